@@ -15,7 +15,7 @@ fn main() {
 
 #[cfg(feature = "proc_macro")]
 fn main() {
-    use genawaiter::{rc::gen, yield_};
+    use genawaiter::{into_iter, yield_};
 
     #[derive(Debug)]
     pub struct List<T> {
@@ -30,6 +30,7 @@ fn main() {
         next: Link<T>,
     }
 
+    #[into_iter]
     impl<T> List<T> {
         fn new() -> Self {
             Self { head: None }
@@ -43,15 +44,13 @@ fn main() {
             self.head = Some(new_head);
         }
 
+        #[into_iter]
         fn iter(&self) -> impl Iterator<Item = &T> {
             let mut current = &self.head;
-            gen!({
-                while let Some(next) = current {
-                    yield_!(&next.val);
-                    current = &next.next;
-                }
-            })
-            .into_iter()
+            while let Some(next) = current {
+                yield_!(&next.val);
+                current = &next.next;
+            }
         }
     }
 
@@ -64,7 +63,12 @@ fn main() {
         list.push(13);
 
         for x in list.iter() {
-            println!("{:?}", x);
+            println!("{x:?}");
+        }
+
+        // `&List` implements `IntoIterator`, through `iter`.
+        for x in &list {
+            println!("{x:?}");
         }
     }
 
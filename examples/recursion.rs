@@ -16,11 +16,11 @@ use genawaiter::sync::{Gen, GenBoxed};
 
 fn main() {
     for n in countdown(10) {
-        println!("{}", n);
+        println!("{n}");
     }
 }
 
-fn countdown(start: i32) -> GenBoxed<i32> {
+fn countdown(start: i32) -> GenBoxed<'static, i32> {
     Gen::new_boxed(|mut co| {
         async move {
             if start == 0 {
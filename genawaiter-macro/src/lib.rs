@@ -5,10 +5,10 @@
 #[macro_export]
 #[cfg(feature = "proc_macro")]
 macro_rules! stack_let_gen {
-    ($name:ident, $body:expr $(,)?) => {
+    ($name:ident, $($body:tt)*) => {
         ::genawaiter::stack::let_gen_using!(
             $name,
-            ::genawaiter::stack_producer!($body),
+            ::genawaiter::stack_producer!($($body)*),
         );
     };
 }
@@ -16,6 +16,9 @@ macro_rules! stack_let_gen {
 #[macro_export]
 macro_rules! stack_let_gen_using {
     ($name:ident, $producer:expr $(,)?) => {
+        ::genawaiter::stack::let_gen_using!(@__impl new, $name, $producer);
+    };
+    (@__impl $new:ident, $name:ident, $producer:expr) => {
         // Safety: The goal here is to ensure the safety invariants of `Gen::new`, i.e.,
         // the lifetime of the `Co` argument (in `$producer`) must not outlive `shelf`
         // or `generator`.
@@ -27,25 +30,99 @@ macro_rules! stack_let_gen_using {
         // `Gen::new` ties the lifetime of `co` to that of `shelf`. This means it has
         // the same lifetime as `generator`, and so the invariant of `Gen::new` cannot
         // be violated.
-        let mut shelf = ::genawaiter::stack::Shelf::new();
+        let mut shelf = ::genawaiter::stack::Shelf::$new();
         let mut generator =
             unsafe { ::genawaiter::stack::Gen::new(&mut shelf, $producer) };
         let $name = &mut generator;
     };
 }
 
+#[macro_export]
+#[cfg(feature = "proc_macro")]
+macro_rules! stack_let_send_gen {
+    ($name:ident, $($body:tt)*) => {
+        ::genawaiter::stack::let_send_gen_using!(
+            $name,
+            ::genawaiter::stack_send_producer!($($body)*),
+        );
+    };
+}
+
+#[macro_export]
+macro_rules! stack_let_send_gen_using {
+    ($name:ident, $producer:expr $(,)?) => {
+        ::genawaiter::stack::let_gen_using!(@__impl new_send, $name, $producer);
+    };
+}
+
 #[macro_export]
 #[cfg(feature = "proc_macro")]
 macro_rules! rc_gen {
-    ($body:expr) => {
-        ::genawaiter::rc::Gen::new(::genawaiter::rc_producer!($body))
+    ($($body:tt)*) => {
+        ::genawaiter::rc::Gen::new(::genawaiter::rc_producer!($($body)*))
     };
 }
 
 #[macro_export]
 #[cfg(feature = "proc_macro")]
 macro_rules! sync_gen {
-    ($body:expr) => {
-        ::genawaiter::sync::Gen::new(::genawaiter::sync_producer!($body))
+    ($($body:tt)*) => {
+        ::genawaiter::sync::Gen::new(::genawaiter::sync_producer!($($body)*))
+    };
+}
+
+#[macro_export]
+#[cfg(feature = "proc_macro")]
+macro_rules! rc_iter_gen {
+    ($($body:tt)*) => {
+        ::genawaiter::rc::Gen::new(::genawaiter::iter_producer!(rc, $($body)*))
+    };
+}
+
+#[macro_export]
+#[cfg(feature = "proc_macro")]
+macro_rules! sync_iter_gen {
+    ($($body:tt)*) => {
+        ::genawaiter::sync::Gen::new(::genawaiter::iter_producer!(sync, $($body)*))
+    };
+}
+
+#[macro_export]
+#[cfg(feature = "proc_macro")]
+macro_rules! rc_stream {
+    ($($body:tt)*) => {
+        ::genawaiter::__private::into_stream(::genawaiter::rc::Gen::new(
+            ::genawaiter::stream_producer!(rc, $($body)*),
+        ))
+    };
+}
+
+#[macro_export]
+#[cfg(feature = "proc_macro")]
+macro_rules! rc_try_stream {
+    ($($body:tt)*) => {
+        ::genawaiter::__private::into_stream(::genawaiter::rc::Gen::new(
+            ::genawaiter::try_stream_producer!(rc, $($body)*),
+        ))
+    };
+}
+
+#[macro_export]
+#[cfg(feature = "proc_macro")]
+macro_rules! sync_stream {
+    ($($body:tt)*) => {
+        ::genawaiter::__private::into_stream(::genawaiter::sync::Gen::new(
+            ::genawaiter::stream_producer!(sync, $($body)*),
+        ))
+    };
+}
+
+#[macro_export]
+#[cfg(feature = "proc_macro")]
+macro_rules! sync_try_stream {
+    ($($body:tt)*) => {
+        ::genawaiter::__private::into_stream(::genawaiter::sync::Gen::new(
+            ::genawaiter::try_stream_producer!(sync, $($body)*),
+        ))
     };
 }
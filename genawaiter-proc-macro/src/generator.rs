@@ -0,0 +1,220 @@
+use crate::visit::{hidden_co, YieldReplace};
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit_mut::VisitMut,
+    FnArg,
+    GenericArgument,
+    ItemFn,
+    Lifetime,
+    PathArguments,
+    Receiver,
+    ReturnType,
+    Token,
+    Type,
+    TypeParamBound,
+};
+
+/// The arguments of the attribute, e.g. `#[generator(yield = u32)]`.
+pub struct GeneratorArgs {
+    pub yield_ty: Option<Type>,
+}
+
+impl Parse for GeneratorArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self { yield_ty: None });
+        }
+        input.parse::<Token![yield]>()?;
+        input.parse::<Token![=]>()?;
+        let yield_ty = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self {
+            yield_ty: Some(yield_ty),
+        })
+    }
+}
+
+/// How the generator is returned.
+pub enum Output {
+    /// `impl Iterator<Item = T>` or `impl Stream<Item = T>`.
+    Opaque,
+    /// `Box<dyn Iterator<Item = T>>`, e.g., for a trait method.
+    Boxed,
+    /// `Pin<Box<dyn Stream<Item = T>>>`, e.g., for an async trait method.
+    Pinned,
+}
+
+/// Expands `#[generator]`.
+pub fn expand(args: GeneratorArgs, mut function: ItemFn) -> TokenStream2 {
+    let receiver_lifetime = match function.sig.inputs.first() {
+        Some(FnArg::Receiver(Receiver {
+            reference: Some((and, lifetime)),
+            ..
+        })) => {
+            Some(
+                lifetime
+                    .clone()
+                    .unwrap_or_else(|| Lifetime::new("'_", and.span())),
+            )
+        }
+        _ => None,
+    };
+
+    let output_span = function.sig.output.span();
+    let (output, bounds) = match &mut function.sig.output {
+        ReturnType::Type(_, ty) => return_bounds(ty),
+        ReturnType::Default => abort!(function.sig.span(), "{}", RETURN_TYPE_HELP),
+    };
+    // A method's generator usually borrows `self`, but before the 2024 edition,
+    // neither an `impl Trait` nor a boxed `dyn Trait` captures that lifetime
+    // unless it is named.
+    if let Some(lifetime) = receiver_lifetime {
+        if !bounds
+            .iter()
+            .any(|bound| matches!(bound, TypeParamBound::Lifetime(_)))
+        {
+            bounds.push(TypeParamBound::Lifetime(lifetime));
+        }
+    }
+    let bounds = &*bounds;
+
+    let yield_ty = match args.yield_ty {
+        Some(ty) => ty,
+        None => {
+            item_type(bounds).cloned().unwrap_or_else(|| {
+                abort!(
+                    output_span,
+                    "The yield type could not be found. Write it in the return type, \
+                     as `Item = T`, or in the attribute, as `#[generator(yield = T)]`.",
+                )
+            })
+        }
+    };
+    // A `Send` generator needs the thread-safe engine.
+    let engine = if bounds.iter().any(|bound| is_trait(bound, "Send")) {
+        quote! { sync }
+    } else {
+        quote! { rc }
+    };
+
+    let mut block = function.block;
+    YieldReplace::new().visit_block_mut(&mut block);
+    let co = hidden_co();
+    let gen = quote! {
+        ::genawaiter::#engine::Gen::new(
+            move |mut #co: ::genawaiter::#engine::Co<#yield_ty>| {
+                async move #block
+            },
+        )
+    };
+    // An `async fn` becomes an ordinary function which returns a stream.
+    let body = if function.sig.asyncness.take().is_some() {
+        gen
+    } else {
+        quote! { ::std::iter::IntoIterator::into_iter(#gen) }
+    };
+    let body = match output {
+        Output::Opaque => body,
+        Output::Boxed => quote! { ::std::boxed::Box::new(#body) },
+        Output::Pinned => quote! { ::std::boxed::Box::pin(#body) },
+    };
+
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let sig = &function.sig;
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #body
+        }
+    }
+}
+
+const RETURN_TYPE_HELP: &str =
+    "A `generator` must declare its return type as `impl Iterator<Item = T>`, or as \
+     `impl Stream<Item = T>` if it is `async`. To return a trait object, use `Box<dyn \
+     Iterator<Item = T>>` or `Pin<Box<dyn Stream<Item = T>>>`.";
+
+/// Finds the trait bounds in the return type, which is either `impl Trait`,
+/// `Box<dyn Trait>`, or `Pin<Box<dyn Trait>>`.
+pub fn return_bounds(
+    ty: &mut Type,
+) -> (Output, &mut Punctuated<TypeParamBound, Token![+]>) {
+    let span = ty.span();
+    match ty {
+        Type::ImplTrait(ty) => (Output::Opaque, &mut ty.bounds),
+        Type::Path(path) => {
+            let Some(seg) = path.path.segments.last_mut() else {
+                abort!(span, "{}", RETURN_TYPE_HELP)
+            };
+            let output = match &*seg.ident.to_string() {
+                "Box" => Output::Boxed,
+                "Pin" => Output::Pinned,
+                _ => abort!(span, "{}", RETURN_TYPE_HELP),
+            };
+            let inner = match &mut seg.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match &mut args.args[0] {
+                        GenericArgument::Type(inner) => inner,
+                        _ => abort!(span, "{}", RETURN_TYPE_HELP),
+                    }
+                }
+                _ => abort!(span, "{}", RETURN_TYPE_HELP),
+            };
+            match (output, inner) {
+                (Output::Boxed, Type::TraitObject(object)) => {
+                    (Output::Boxed, &mut object.bounds)
+                }
+                (Output::Pinned, inner @ Type::Path(_)) => {
+                    match return_bounds(inner) {
+                        (Output::Boxed, bounds) => (Output::Pinned, bounds),
+                        _ => abort!(span, "{}", RETURN_TYPE_HELP),
+                    }
+                }
+                _ => abort!(span, "{}", RETURN_TYPE_HELP),
+            }
+        }
+        _ => abort!(span, "{}", RETURN_TYPE_HELP),
+    }
+}
+
+/// Finds `T` in `impl Iterator<Item = T>` or `impl Stream<Item = T>`.
+pub fn item_type(bounds: &Punctuated<TypeParamBound, Token![+]>) -> Option<&Type> {
+    bounds.iter().find_map(|bound| {
+        let TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let PathArguments::AngleBracketed(args) =
+            &bound.path.segments.last()?.arguments
+        else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| {
+            match arg {
+                GenericArgument::Binding(binding) if binding.ident == "Item" => {
+                    Some(&binding.ty)
+                }
+                _ => None,
+            }
+        })
+    })
+}
+
+/// Whether `bound` names the trait `name`, by any path.
+fn is_trait(bound: &TypeParamBound, name: &str) -> bool {
+    match bound {
+        TypeParamBound::Trait(bound) => {
+            bound
+                .path
+                .segments
+                .last()
+                .is_some_and(|seg| seg.ident == name)
+        }
+        TypeParamBound::Lifetime(_) => false,
+    }
+}
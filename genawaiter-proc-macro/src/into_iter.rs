@@ -0,0 +1,272 @@
+use crate::generator::{self, GeneratorArgs, Output};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{
+    parse_quote,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    Attribute,
+    FnArg,
+    GenericParam,
+    Generics,
+    ImplItem,
+    ImplItemMethod,
+    ItemFn,
+    ItemImpl,
+    Lifetime,
+    LifetimeDef,
+    Receiver,
+    ReturnType,
+    Type,
+    TypeBareFn,
+    TypePath,
+    TypeReference,
+};
+
+/// Expands `#[into_iter]` on an `impl` block.
+pub fn expand(mut item: ItemImpl) -> TokenStream2 {
+    if let Some((_, path, _)) = &item.trait_ {
+        abort!(
+            path.span(),
+            "`#[into_iter]` must be placed on an inherent `impl` block, not a trait \
+             implementation.",
+        );
+    }
+
+    let mut impls = Vec::new();
+    // Whether a method taking `&self`, and one taking `&mut self`, were found.
+    let mut found = (false, false);
+    for impl_item in &mut item.items {
+        let ImplItem::Method(method) = impl_item else {
+            continue;
+        };
+        let Some(index) = method.attrs.iter().position(is_into_iter) else {
+            continue;
+        };
+        let attr = method.attrs.remove(index);
+        let args = if attr.tokens.is_empty() {
+            GeneratorArgs { yield_ty: None }
+        } else {
+            attr.parse_args()
+                .unwrap_or_else(|err| abort!(err.span(), "{}", err))
+        };
+
+        let (mutability, lifetime) = receiver(method);
+        let found = if mutability {
+            &mut found.1
+        } else {
+            &mut found.0
+        };
+        if *found {
+            abort!(
+                attr.span(),
+                "Only one method per kind of receiver can be marked `#[into_iter]`.",
+            );
+        }
+        *found = true;
+
+        let (tokens, into_iter) =
+            expand_method(&item.self_ty, &item.generics, method, args, lifetime);
+        *impl_item = ImplItem::Verbatim(tokens);
+        impls.push(into_iter);
+    }
+    if impls.is_empty() {
+        abort!(
+            item.impl_token.span,
+            "Mark the generator method with `#[into_iter]` as well, to choose the \
+             method which `into_iter` calls.",
+        );
+    }
+
+    quote! {
+        #item
+        #(#impls)*
+    }
+}
+
+/// The error for `#[into_iter]` on anything but an `impl` block.
+pub fn misplaced(span: Span) -> ! {
+    abort!(
+        span,
+        "`#[into_iter]` must be placed on the `impl` block, as well as on the \
+         generator method, since that is where `IntoIterator` is implemented.",
+    )
+}
+
+fn is_into_iter(attr: &Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|seg| seg.ident == "into_iter")
+}
+
+/// Whether the method takes `&mut self`, and the lifetime it names for `self`,
+/// if any.
+fn receiver(method: &ImplItemMethod) -> (bool, Option<Lifetime>) {
+    let sig = &method.sig;
+    match sig.inputs.first() {
+        Some(FnArg::Receiver(Receiver {
+            reference: Some((_, lifetime)),
+            mutability,
+            ..
+        })) if sig.inputs.len() == 1 => {
+            let only_self_lifetime = sig.generics.params.iter().all(|param| {
+                match param {
+                    GenericParam::Lifetime(def) => {
+                        Some(&def.lifetime) == lifetime.as_ref()
+                    }
+                    _ => false,
+                }
+            });
+            if sig.asyncness.is_some() || !only_self_lifetime {
+                abort!(
+                    sig.span(),
+                    "An `#[into_iter]` method cannot be `async`, or have generic \
+                     parameters other than the lifetime of `self`.",
+                );
+            }
+            (mutability.is_some(), lifetime.clone())
+        }
+        _ => {
+            abort!(
+                sig.span(),
+                "An `#[into_iter]` method must take `&self` or `&mut self`, and no \
+                 other arguments.",
+            )
+        }
+    }
+}
+
+/// Turns the method into a generator, and implements `IntoIterator` for a
+/// reference to `self_ty` by calling it.
+fn expand_method(
+    self_ty: &Type,
+    generics: &Generics,
+    method: &mut ImplItemMethod,
+    mut args: GeneratorArgs,
+    lifetime: Option<Lifetime>,
+) -> (TokenStream2, TokenStream2) {
+    // Without a return type, the method returns a boxed iterator.
+    if let ReturnType::Default = method.sig.output {
+        let Some(yield_ty) = &args.yield_ty else {
+            abort!(
+                method.sig.span(),
+                "The yield type could not be found. Write it in the attribute, as \
+                 `#[into_iter(yield = T)]`, or in the return type, as `impl \
+                 Iterator<Item = T>`.",
+            );
+        };
+        let iterator = quote! { dyn ::std::iter::Iterator<Item = #yield_ty> };
+        method.sig.output = parse_quote!(-> ::std::boxed::Box<#iterator>);
+    }
+    let ReturnType::Type(_, output_ty) = &method.sig.output else {
+        unreachable!("the return type was filled in above")
+    };
+    let mut output_ty = (**output_ty).clone();
+    let (output, bounds) = generator::return_bounds(&mut output_ty);
+    if let Output::Pinned = output {
+        abort!(
+            output_ty.span(),
+            "An `#[into_iter]` method must return an iterator, not a stream.",
+        );
+    }
+    if args.yield_ty.is_none() {
+        args.yield_ty = generator::item_type(bounds).cloned();
+    }
+    let Some(mut item_ty) = args.yield_ty.clone() else {
+        abort!(
+            output_ty.span(),
+            "The yield type could not be found. Write it in the return type, as `Item \
+             = T`, or in the attribute, as `#[into_iter(yield = T)]`.",
+        )
+    };
+
+    // The item type may borrow from `self` through elided lifetimes, which are
+    // named in the `IntoIterator` implementation.
+    let lifetime = lifetime
+        .unwrap_or_else(|| Lifetime::new("'__genawaiter_self", Span::call_site()));
+    ItemLifetimes {
+        lifetime: &lifetime,
+        self_ty,
+    }
+    .visit_type_mut(&mut item_ty);
+
+    let function = ItemFn {
+        attrs: method.attrs.clone(),
+        vis: method.vis.clone(),
+        sig: method.sig.clone(),
+        block: Box::new(method.block.clone()),
+    };
+    let tokens = generator::expand(args, function);
+    // A trait object can be named, unlike the type of the generator.
+    let ident = &method.sig.ident;
+    let call = quote! { <#self_ty>::#ident(self) };
+    let call = match output {
+        Output::Opaque => quote! { ::std::boxed::Box::new(#call) },
+        Output::Boxed => call,
+        Output::Pinned => unreachable!("streams were rejected above"),
+    };
+
+    let mutability = match method.sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) => receiver.mutability,
+        _ => None,
+    };
+    // The implementation has the generics of the `impl` block, plus the
+    // lifetime of `self`.
+    let mut generics = generics.clone();
+    generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let into_iter = quote! {
+        impl #impl_generics ::std::iter::IntoIterator for &#lifetime #mutability #self_ty
+        #where_clause
+        {
+            type Item = #item_ty;
+            type IntoIter =
+                ::std::boxed::Box<dyn ::std::iter::Iterator<Item = #item_ty> + #lifetime>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                #call
+            }
+        }
+    };
+    (tokens, into_iter)
+}
+
+/// Names the elided lifetimes in an item type, and replaces `Self`, which means
+/// something else in the `IntoIterator` implementation.
+struct ItemLifetimes<'a> {
+    lifetime: &'a Lifetime,
+    self_ty: &'a Type,
+}
+
+impl VisitMut for ItemLifetimes<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(TypePath { qself: None, path }) = ty {
+            if path.is_ident("Self") {
+                *ty = self.self_ty.clone();
+                return;
+            }
+        }
+        visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        if ty.lifetime.is_none() {
+            ty.lifetime = Some(self.lifetime.clone());
+        }
+        visit_mut::visit_type_reference_mut(self, ty);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.lifetime.clone();
+        }
+    }
+
+    // Elided lifetimes in function pointers are higher-ranked.
+    fn visit_type_bare_fn_mut(&mut self, _ty: &mut TypeBareFn) {}
+}
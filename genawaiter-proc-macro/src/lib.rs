@@ -2,179 +2,123 @@
 #![warn(clippy::cargo, clippy::pedantic)]
 #![cfg_attr(feature = "strict", deny(warnings))]
 
-extern crate proc_macro;
-
-use crate::visit::YieldReplace;
+use crate::{
+    generator::GeneratorArgs,
+    producer::{IterProducerInput, ProducerInput},
+    producer_fn::{Engine, ProducerArgs},
+};
 use proc_macro::TokenStream;
-use proc_macro_error::{abort, abort_call_site, proc_macro_error};
+use proc_macro_error::proc_macro_error;
 use proc_macro_hack::proc_macro_hack;
-use quote::quote;
-use std::string::ToString;
-use syn::{
-    self,
-    parse_macro_input,
-    parse_str,
-    spanned::Spanned,
-    visit_mut::VisitMut,
-    ExprBlock,
-    FnArg,
-    Ident,
-    ItemFn,
-    Type,
-};
+use syn::{self, parse_macro_input, spanned::Spanned, Item, ItemFn};
 
+mod generator;
+mod into_iter;
+mod producer;
+mod producer_fn;
+mod stream;
 mod visit;
 
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn stack_producer_fn(args: TokenStream, input: TokenStream) -> TokenStream {
-    let a = args.clone();
-    // make sure it is a valid type
-    let _ = parse_macro_input!(a as Type);
-    let mut function = parse_macro_input!(input as ItemFn);
-
-    let co_arg = format!("{}{}>", stack::CO_ARG_FN, args);
-    add_coroutine_arg(&mut function, &co_arg);
-
-    YieldReplace.visit_item_fn_mut(&mut function);
-
-    let tokens = quote! { #function };
-    tokens.into()
+    let args = parse_macro_input!(args as ProducerArgs);
+    let function = parse_macro_input!(input as ItemFn);
+    producer_fn::expand(Engine::Stack, &args, function).into()
 }
 
 #[proc_macro_hack]
 #[proc_macro_error]
 pub fn stack_producer(input: TokenStream) -> TokenStream {
-    let mut input = parse_macro_input!(input as ExprBlock);
-
-    YieldReplace.visit_expr_block_mut(&mut input);
-    // for some reason parsing as a PatType (correct for closures) fails
-    // the only way around is to destructure.
-    let arg = match parse_str::<FnArg>(stack::CO_ARG) {
-        Ok(FnArg::Typed(x)) => x,
-        _ => abort_call_site!("string Pat parse failed Co<...>"),
-    };
+    let input = parse_macro_input!(input as ProducerInput);
+    producer::expand(Engine::Stack, input).into()
+}
 
-    let tokens = quote! { |#arg| async move #input };
-    tokens.into()
+#[proc_macro_hack]
+#[proc_macro_error]
+pub fn stack_send_producer(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ProducerInput);
+    producer::expand(Engine::SendStack, input).into()
 }
 
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn sync_producer_fn(args: TokenStream, input: TokenStream) -> TokenStream {
-    let a = args.clone();
-    // make sure it is a valid type
-    let _ = parse_macro_input!(a as Type);
-    let mut function = parse_macro_input!(input as ItemFn);
-
-    let co_arg = format!("{}{}>", sync::CO_ARG_FN, args);
-    add_coroutine_arg(&mut function, &co_arg);
-
-    YieldReplace.visit_item_fn_mut(&mut function);
-
-    let tokens = quote! { #function };
-    tokens.into()
+    let args = parse_macro_input!(args as ProducerArgs);
+    let function = parse_macro_input!(input as ItemFn);
+    producer_fn::expand(Engine::Sync, &args, function).into()
 }
 
 #[proc_macro_hack]
 #[proc_macro_error]
 pub fn sync_producer(input: TokenStream) -> TokenStream {
-    let mut input = parse_macro_input!(input as ExprBlock);
-
-    YieldReplace.visit_expr_block_mut(&mut input);
-    // for some reason parsing as a PatType (correct for closures) fails
-    let arg = match parse_str::<FnArg>(sync::CO_ARG) {
-        Ok(FnArg::Typed(x)) => x,
-        _ => abort_call_site!("string Pat parse failed Co<...>"),
-    };
-
-    let tokens = quote! { |#arg| async move #input };
-    tokens.into()
+    let input = parse_macro_input!(input as ProducerInput);
+    producer::expand(Engine::Sync, input).into()
 }
 
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn rc_producer_fn(args: TokenStream, input: TokenStream) -> TokenStream {
-    let a = args.clone();
-    // make sure it is a valid type
-    let _ = parse_macro_input!(a as Type);
-    let mut function = parse_macro_input!(input as ItemFn);
-
-    let co_arg = format!("{}{}>", rc::CO_ARG_FN, args);
-    add_coroutine_arg(&mut function, &co_arg);
-
-    YieldReplace.visit_item_fn_mut(&mut function);
-
-    let tokens = quote! { #function };
-    tokens.into()
+    let args = parse_macro_input!(args as ProducerArgs);
+    let function = parse_macro_input!(input as ItemFn);
+    producer_fn::expand(Engine::Rc, &args, function).into()
 }
 
 #[proc_macro_hack]
 #[proc_macro_error]
 pub fn rc_producer(input: TokenStream) -> TokenStream {
-    let mut input = parse_macro_input!(input as ExprBlock);
+    let input = parse_macro_input!(input as ProducerInput);
+    producer::expand(Engine::Rc, input).into()
+}
 
-    YieldReplace.visit_expr_block_mut(&mut input);
-    // for some reason parsing as a PatType (correct for closures) fails
-    let arg = match parse_str::<FnArg>(rc::CO_ARG) {
-        Ok(FnArg::Typed(x)) => x,
-        _ => abort_call_site!("string Pat parse failed Co<...>"),
-    };
+#[proc_macro_hack]
+#[proc_macro_error]
+pub fn iter_producer(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as IterProducerInput);
+    producer::expand_iter(input).into()
+}
 
-    let tokens = quote! { |#arg| async move #input };
-    tokens.into()
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn producer_fn(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as ProducerArgs);
+    let function = parse_macro_input!(input as ItemFn);
+    producer_fn::expand(Engine::Generic, &args, function).into()
 }
 
-mod stack {
-    pub(crate) const CO_ARG_FN: &str =
-        "mut __private_co_arg__: ::genawaiter::stack::Co<'_, ";
-    pub(crate) const CO_ARG: &str =
-        "mut __private_co_arg__: ::genawaiter::stack::Co<'_, _, _>";
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn generator(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as GeneratorArgs);
+    let function = parse_macro_input!(input as ItemFn);
+    generator::expand(args, function).into()
 }
 
-mod sync {
-    pub(crate) const CO_ARG_FN: &str =
-        "mut __private_co_arg__: ::genawaiter::sync::Co<";
-    pub(crate) const CO_ARG: &str =
-        "mut __private_co_arg__: ::genawaiter::sync::Co<_, _>";
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn into_iter(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = match parse_macro_input!(input as Item) {
+        Item::Impl(item) => item,
+        item => into_iter::misplaced(item.span()),
+    };
+    let args = proc_macro2::TokenStream::from(args);
+    if let Some(token) = args.into_iter().next() {
+        proc_macro_error::abort!(
+            token.span(),
+            "Arguments such as `yield = T` belong on the method's `#[into_iter]`.",
+        );
+    }
+    into_iter::expand(item).into()
 }
 
-mod rc {
-    pub(crate) const CO_ARG_FN: &str = "mut __private_co_arg__: ::genawaiter::rc::Co<";
-    pub(crate) const CO_ARG: &str =
-        "mut __private_co_arg__: ::genawaiter::rc::Co<_, _>";
+#[proc_macro_hack]
+#[proc_macro_error]
+pub fn stream_producer(input: TokenStream) -> TokenStream {
+    stream::expand(input.into(), false).into()
 }
 
-/// Mutates the input `Punctuated<FnArg, Comma>` to a lifetimeless `co:
-/// Co<{type}>`.
-fn add_coroutine_arg(func: &mut ItemFn, co_ty: &str) {
-    let co_arg_found = func.sig.inputs.iter().any(|input| {
-        match input {
-            FnArg::Receiver(_) => false,
-            FnArg::Typed(arg) => {
-                match &*arg.ty {
-                    Type::Path(ty) => {
-                        ty.path.segments.iter().any(|seg| {
-                            seg.ident
-                                == parse_str::<Ident>("Co").expect("Ident parse failed")
-                        })
-                    }
-                    _ => false,
-                }
-            }
-        }
-    });
-    if !co_arg_found {
-        let co_arg: FnArg = match parse_str::<FnArg>(co_ty) {
-            Ok(s) => s,
-            Err(err) => abort_call_site!(format!("invalid type for Co yield {}", err)),
-        };
-        func.sig.inputs.push_value(co_arg)
-    } else {
-        abort!(
-            func.sig.span(),
-            "A generator producer cannot accept any arguments. Instead, consider \
-             using a closure and capturing the values you need.",
-        )
-    }
+#[proc_macro_hack]
+#[proc_macro_error]
+pub fn try_stream_producer(input: TokenStream) -> TokenStream {
+    stream::expand(input.into(), true).into()
 }
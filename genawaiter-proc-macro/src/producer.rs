@@ -0,0 +1,170 @@
+use crate::{
+    producer_fn::{Engine, ProducerArgs},
+    visit::{hidden_co, YieldReplace},
+};
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    token,
+    visit_mut::VisitMut,
+    Expr,
+    ExprBlock,
+    Token,
+    Type,
+};
+
+/// The input of `producer!` and `gen!`: a block, optionally preceded by a
+/// signature, as in `yield i32, resume &str, return usize => { ... }`, and by a
+/// name for the `Co`, as in `|co| { ... }`.
+pub struct ProducerInput {
+    yield_ty: Option<Type>,
+    resume_ty: Option<Type>,
+    return_ty: Option<Type>,
+    co: Option<Ident>,
+    body: ExprBlock,
+}
+
+impl Parse for ProducerInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut yield_ty = None;
+        let mut resume_ty = None;
+        let mut return_ty = None;
+        if !input.peek(token::Brace) && !input.peek(Token![|]) {
+            loop {
+                let lookahead = input.lookahead1();
+                let (slot, keyword, span) = if lookahead.peek(Token![yield]) {
+                    let token = input.parse::<Token![yield]>()?;
+                    (&mut yield_ty, "yield", token.span)
+                } else if lookahead.peek(Token![return]) {
+                    let token = input.parse::<Token![return]>()?;
+                    (&mut return_ty, "return", token.span)
+                } else if input.peek(syn::Ident)
+                    && input.fork().parse::<Ident>()? == "resume"
+                {
+                    let token = input.parse::<Ident>()?;
+                    (&mut resume_ty, "resume", token.span())
+                } else {
+                    return Err(lookahead.error());
+                };
+                if slot.is_some() {
+                    let message = format!("`{keyword}` was given more than once");
+                    return Err(syn::Error::new(span, message));
+                }
+                *slot = Some(input.parse()?);
+
+                if input.peek(Token![=>]) {
+                    input.parse::<Token![=>]>()?;
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+                if input.peek(Token![=>]) {
+                    input.parse::<Token![=>]>()?;
+                    break;
+                }
+            }
+        }
+        let co = if input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            input.parse::<Option<Token![mut]>>()?;
+            let co = input.parse()?;
+            input.parse::<Token![|]>()?;
+            Some(co)
+        } else {
+            None
+        };
+        // Like a closure, a named `Co` can be followed by any expression.
+        let body = if co.is_some() && !input.peek(token::Brace) {
+            let expr: Expr = input.parse()?;
+            parse_quote!({ #expr })
+        } else {
+            input.parse()?
+        };
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self {
+            yield_ty,
+            resume_ty,
+            return_ty,
+            co,
+            body,
+        })
+    }
+}
+
+/// The input of `iter_producer!`: the engine's name, then a `producer!` input.
+pub struct IterProducerInput {
+    engine: Engine,
+    input: ProducerInput,
+}
+
+impl Parse for IterProducerInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        let engine = match &*ident.to_string() {
+            "rc" => Engine::Rc,
+            "sync" => Engine::Sync,
+            _ => return Err(syn::Error::new(ident.span(), "expected `rc` or `sync`")),
+        };
+        input.parse::<Token![,]>()?;
+        Ok(Self {
+            engine,
+            input: input.parse()?,
+        })
+    }
+}
+
+/// Expands a `producer!` into a closure for the given engine.
+pub fn expand(engine: Engine, input: ProducerInput) -> TokenStream2 {
+    expand_using(engine, input, false)
+}
+
+/// Expands an `iter_producer!`, whose body may not await anything except its
+/// own yields.
+pub fn expand_iter(input: IterProducerInput) -> TokenStream2 {
+    expand_using(input.engine, input.input, true)
+}
+
+fn expand_using(
+    engine: Engine,
+    input: ProducerInput,
+    forbid_await: bool,
+) -> TokenStream2 {
+    let ProducerInput {
+        yield_ty,
+        resume_ty,
+        return_ty,
+        co,
+        mut body,
+    } = input;
+
+    let args = ProducerArgs {
+        yield_ty: yield_ty.unwrap_or_else(|| parse_quote!(_)),
+        resume_ty: resume_ty.unwrap_or_else(|| parse_quote!(_)),
+    };
+    let co_ty = engine.co_type(&args);
+    let co = co.unwrap_or_else(hidden_co);
+    let mut replace = YieldReplace::using(co.clone());
+    if forbid_await {
+        replace = replace.forbid_await();
+    }
+    replace.visit_expr_block_mut(&mut body);
+    // The body may only pass a named `Co` along, without yielding through it.
+    let co_arg = quote! { #[allow(unused_mut)] mut #co: #co_ty };
+
+    // An async block cannot declare its output type, so it is checked by
+    // binding the body's value.
+    if let Some(return_ty) = return_ty {
+        let value = Ident::new("__value", Span::mixed_site());
+        quote! {
+            |#co_arg| async move {
+                let #value: #return_ty = #body;
+                // This is unreachable if the body always returns early.
+                #[allow(unreachable_code)]
+                return #value;
+            }
+        }
+    } else {
+        quote! { |#co_arg| async move #body }
+    }
+}
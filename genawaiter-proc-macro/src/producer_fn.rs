@@ -0,0 +1,399 @@
+use crate::visit::{hidden_co, YieldReplace};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    FnArg,
+    GenericParam,
+    Ident,
+    ItemFn,
+    Lifetime,
+    LifetimeDef,
+    Pat,
+    Path,
+    ReturnType,
+    Signature,
+    Token,
+    Type,
+    TypeBareFn,
+    TypeImplTrait,
+    TypeReference,
+};
+
+/// The arguments of the attribute, e.g. `#[producer_fn(u8, resume = &str)]`.
+pub struct ProducerArgs {
+    pub yield_ty: Type,
+    pub resume_ty: Type,
+}
+
+impl Parse for ProducerArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let yield_ty = input.parse()?;
+        let mut resume_ty = parse_quote!(());
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "resume" {
+                return Err(syn::Error::new(key.span(), "expected `resume = <type>`"));
+            }
+            input.parse::<Token![=]>()?;
+            resume_ty = input.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self {
+            yield_ty,
+            resume_ty,
+        })
+    }
+}
+
+/// The engine whose `Co` the producer accepts.
+#[derive(Clone, Copy)]
+pub enum Engine {
+    Rc,
+    Sync,
+    Stack,
+    /// A stack generator which is `Send`.
+    SendStack,
+    /// Any engine, through `Co<impl Airlock>`.
+    Generic,
+}
+
+/// The name of the lifetime (for `stack`) or type parameter (for `Generic`)
+/// which a producer with arguments is generic over.
+const ENGINE_PARAM: &str = "__GenawaiterEngine";
+
+impl Engine {
+    /// The type of the `Co` argument of a producer without arguments, or of a
+    /// producer closure.
+    pub fn co_type(self, args: &ProducerArgs) -> TokenStream2 {
+        let ProducerArgs {
+            yield_ty: y,
+            resume_ty: r,
+        } = args;
+        match self {
+            Self::Rc => quote! { ::genawaiter::rc::Co<#y, #r> },
+            Self::Sync => quote! { ::genawaiter::sync::Co<#y, #r> },
+            Self::Stack => quote! { ::genawaiter::stack::Co<'_, #y, #r> },
+            Self::SendStack => quote! { ::genawaiter::stack::SendCo<'_, #y, #r> },
+            Self::Generic => {
+                quote! {
+                    ::genawaiter::Co<impl ::genawaiter::Airlock<Yield = #y, Resume = #r>>
+                }
+            }
+        }
+    }
+
+    /// The generic parameter which a producer with arguments needs in order to
+    /// name its `Co` type, if any.
+    fn param(self, args: &ProducerArgs) -> Option<GenericParam> {
+        let ProducerArgs {
+            yield_ty: y,
+            resume_ty: r,
+        } = args;
+        match self {
+            Self::Rc | Self::Sync => None,
+            Self::Stack | Self::SendStack => {
+                let lifetime = engine_lifetime();
+                Some(parse_quote!(#lifetime))
+            }
+            Self::Generic => {
+                let ident = Ident::new(ENGINE_PARAM, Span::call_site());
+                Some(parse_quote! {
+                    #ident: ::genawaiter::Airlock<Yield = #y, Resume = #r>
+                })
+            }
+        }
+    }
+
+    /// The type of the `Co` argument of a producer with arguments.
+    fn named_co_type(self, args: &ProducerArgs) -> TokenStream2 {
+        let ProducerArgs {
+            yield_ty: y,
+            resume_ty: r,
+        } = args;
+        match self {
+            Self::Rc | Self::Sync => self.co_type(args),
+            Self::Stack => {
+                let lifetime = engine_lifetime();
+                quote! { ::genawaiter::stack::Co<#lifetime, #y, #r> }
+            }
+            Self::SendStack => {
+                let lifetime = engine_lifetime();
+                quote! { ::genawaiter::stack::SendCo<#lifetime, #y, #r> }
+            }
+            Self::Generic => {
+                let ident = Ident::new(ENGINE_PARAM, Span::call_site());
+                quote! { ::genawaiter::Co<#ident> }
+            }
+        }
+    }
+}
+
+fn engine_lifetime() -> Lifetime {
+    Lifetime::new(&format!("'{ENGINE_PARAM}"), Span::call_site())
+}
+
+/// Expands `#[producer_fn]` for the given engine.
+pub fn expand(engine: Engine, args: &ProducerArgs, function: ItemFn) -> TokenStream2 {
+    if let Some(FnArg::Receiver(receiver)) = function.sig.inputs.first() {
+        abort!(
+            receiver.span(),
+            "`producer_fn` cannot be used on a method. Consider moving the body into \
+             a free function, and passing `self` as an argument.",
+        );
+    }
+    reject_co_arg(&function);
+
+    if function.sig.inputs.is_empty() {
+        expand_without_args(engine, args, function)
+    } else {
+        expand_with_args(engine, args, function)
+    }
+}
+
+/// A function without arguments becomes a producer itself, by gaining a `Co`
+/// argument.
+fn expand_without_args(
+    engine: Engine,
+    args: &ProducerArgs,
+    mut function: ItemFn,
+) -> TokenStream2 {
+    let co_ty = engine.co_type(args);
+    let co = hidden_co();
+    function.sig.inputs.push(parse_quote!(mut #co: #co_ty));
+    YieldReplace::new().visit_item_fn_mut(&mut function);
+    quote! { #function }
+}
+
+/// A function with arguments becomes a function which captures its arguments,
+/// and returns a producer.
+fn expand_with_args(
+    engine: Engine,
+    args: &ProducerArgs,
+    function: ItemFn,
+) -> TokenStream2 {
+    let ItemFn {
+        attrs,
+        vis,
+        mut sig,
+        block,
+    } = function;
+
+    add_params(engine, args, &mut sig);
+
+    let co_ty = engine.named_co_type(args);
+    let output = match &sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    let captures = sig.generics.params.iter().map(|param| {
+        match param {
+            GenericParam::Lifetime(def) => {
+                let lifetime = &def.lifetime;
+                quote! { #lifetime }
+            }
+            GenericParam::Type(def) => {
+                let ident = &def.ident;
+                quote! { #ident }
+            }
+            GenericParam::Const(def) => {
+                let ident = &def.ident;
+                quote! { #ident }
+            }
+        }
+    });
+    let captures = quote! { use<#(#captures),*> };
+    let type_args = sig.generics.params.iter().filter_map(|param| {
+        match param {
+            GenericParam::Lifetime(_) => None,
+            GenericParam::Type(def) => Some(&def.ident),
+            GenericParam::Const(def) => Some(&def.ident),
+        }
+    });
+    let turbofish = quote! { ::<#(#type_args),*> };
+
+    // The original function becomes a private producer, nested inside.
+    let mut inner_sig = sig.clone();
+    inner_sig.ident = Ident::new("__producer", Span::call_site());
+    let co = hidden_co();
+    inner_sig.inputs.push(parse_quote!(mut #co: #co_ty));
+    let mut inner = ItemFn {
+        // Lifetimes which were elided are now named, and must stay that way.
+        attrs: vec![parse_quote!(#[allow(clippy::needless_lifetimes)])],
+        vis: syn::Visibility::Inherited,
+        sig: inner_sig,
+        block,
+    };
+    YieldReplace::new().visit_item_fn_mut(&mut inner);
+
+    // The outer function forwards each argument by name, since the original
+    // arguments may be patterns.
+    let mut arg_names = Vec::new();
+    let mut outer_inputs = Punctuated::<FnArg, Token![,]>::new();
+    for (i, input) in sig.inputs.iter().enumerate() {
+        let FnArg::Typed(arg) = input else {
+            unreachable!("receivers were rejected above")
+        };
+        let name = Ident::new(&format!("__arg{i}"), arg.pat.span());
+        let ty = &arg.ty;
+        outer_inputs.push(parse_quote!(#name: #ty));
+        arg_names.push(name);
+    }
+
+    let ident = &sig.ident;
+    let constness = &sig.constness;
+    let unsafety = &sig.unsafety;
+    let generics = &sig.generics;
+    let where_clause = &sig.generics.where_clause;
+    quote! {
+        #(#attrs)*
+        #vis #constness #unsafety fn #ident #generics(#outer_inputs) -> impl ::genawaiter::ProducerFn<
+            #co_ty,
+            Future = impl ::std::future::Future<Output = #output> + #captures,
+        > + #captures
+        #where_clause
+        {
+            #inner
+
+            move |co: #co_ty| __producer #turbofish(#(#arg_names,)* co)
+        }
+    }
+}
+
+/// Adds the generic parameters which the returned producer needs, so that
+/// everything it captures can be named.
+fn add_params(engine: Engine, args: &ProducerArgs, sig: &mut Signature) {
+    // Every lifetime must be named, so the returned producer can capture it.
+    let mut namer = LifetimeNamer::default();
+    for input in &mut sig.inputs {
+        namer.visit_fn_arg_mut(input);
+    }
+    for lifetime in namer.named.into_iter().rev() {
+        sig.generics
+            .params
+            .insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime)));
+    }
+    if let Some(param) = engine.param(args) {
+        let index = match param {
+            GenericParam::Lifetime(_) => 0,
+            _ => sig.generics.params.len(),
+        };
+        sig.generics.params.insert(index, param);
+    }
+    if let Engine::Stack | Engine::SendStack = engine {
+        // Without these bounds, which are implied by the `Co`, the compiler
+        // cannot prove that the returned future captures only the lifetimes
+        // listed in its `use<..>` bound.
+        let lifetime = engine_lifetime();
+        let ProducerArgs {
+            yield_ty: y,
+            resume_ty: r,
+        } = args;
+        let where_clause = sig.generics.make_where_clause();
+        where_clause.predicates.push(parse_quote!(#y: #lifetime));
+        where_clause.predicates.push(parse_quote!(#r: #lifetime));
+    }
+}
+
+/// A producer receives its `Co` from the engine, so the user should not
+/// declare one.
+fn reject_co_arg(function: &ItemFn) {
+    let co_arg = function.sig.inputs.iter().find(|input| {
+        match input {
+            FnArg::Receiver(_) => false,
+            FnArg::Typed(arg) => {
+                match &*arg.ty {
+                    Type::Path(ty) => ty.qself.is_none() && is_co_path(&ty.path),
+                    _ => false,
+                }
+            }
+        }
+    });
+    if let Some(co_arg) = co_arg {
+        let name = match co_arg {
+            FnArg::Typed(arg) => {
+                match &*arg.pat {
+                    Pat::Ident(pat) => pat.ident.to_string(),
+                    _ => "co".to_string(),
+                }
+            }
+            FnArg::Receiver(_) => unreachable!(),
+        };
+        abort!(
+            co_arg.span(),
+            "A `producer_fn` should not accept a `Co` argument. One is added \
+             automatically, and used by `yield_!`. Remove the `{}` argument.",
+            name,
+        )
+    }
+}
+
+/// Whether `path` names one of the crate's `Co` types: `Co`, `genawaiter::Co`,
+/// or `genawaiter::{rc,sync,stack}::Co` (with or without a leading `::`).
+fn is_co_path(path: &Path) -> bool {
+    let segments: Vec<_> = path.segments.iter().map(|seg| &seg.ident).collect();
+    match segments.as_slice() {
+        [co] => path.leading_colon.is_none() && *co == "Co",
+        [krate, co] => *krate == "genawaiter" && *co == "Co",
+        [krate, engine, co] => {
+            *krate == "genawaiter"
+                && (*engine == "rc" || *engine == "sync" || *engine == "stack")
+                && *co == "Co"
+        }
+        _ => false,
+    }
+}
+
+/// Gives a name to each elided lifetime in an argument type, so it can be
+/// captured by the returned producer.
+#[derive(Default)]
+struct LifetimeNamer {
+    named: Vec<Lifetime>,
+}
+
+impl LifetimeNamer {
+    fn next(&mut self, span: Span) -> Lifetime {
+        let lifetime =
+            Lifetime::new(&format!("'__genawaiter_{}", self.named.len()), span);
+        self.named.push(lifetime.clone());
+        lifetime
+    }
+}
+
+impl VisitMut for LifetimeNamer {
+    fn visit_type_reference_mut(&mut self, ty: &mut TypeReference) {
+        if ty.lifetime.is_none() {
+            ty.lifetime = Some(self.next(ty.and_token.span()));
+        }
+        visit_mut::visit_type_reference_mut(self, ty);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.next(lifetime.span());
+        }
+    }
+
+    fn visit_type_impl_trait_mut(&mut self, ty: &mut TypeImplTrait) {
+        abort!(
+            ty.span(),
+            "A `producer_fn` with arguments cannot use `impl Trait` in argument \
+             position. Use a named type parameter instead.",
+        );
+    }
+
+    // Elided lifetimes in function pointers and `Fn` traits are higher-ranked,
+    // so they are left alone.
+    fn visit_type_bare_fn_mut(&mut self, _ty: &mut TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _args: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
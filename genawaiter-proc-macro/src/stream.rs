@@ -0,0 +1,141 @@
+use crate::visit::{hidden_co, YieldReplace};
+use proc_macro2::{
+    Delimiter,
+    Group,
+    Ident,
+    Span,
+    TokenStream as TokenStream2,
+    TokenTree,
+};
+use proc_macro_error::abort_call_site;
+use quote::quote;
+use syn::{parse2, visit_mut::VisitMut, Block};
+
+/// Expands `stream_producer!(engine, { body })` (or `try_stream_producer!`) into
+/// a producer closure for the given engine.
+pub fn expand(input: TokenStream2, fallible: bool) -> TokenStream2 {
+    let mut tokens = input.into_iter();
+    let Some(TokenTree::Ident(engine)) = tokens.next() else {
+        abort_call_site!("expected an engine name")
+    };
+    match tokens.next() {
+        Some(TokenTree::Punct(comma)) if comma.as_char() == ',' => {}
+        _ => abort_call_site!("expected `,` after the engine name"),
+    }
+    let body: TokenStream2 = tokens.collect();
+    // Accept both `stream!({ ... })` and `stream! { ... }`.
+    let body = match body.clone().into_iter().collect::<Vec<_>>().as_slice() {
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Brace => {
+            group.stream()
+        }
+        _ => body,
+    };
+
+    let body = desugar_for_await(body);
+    let mut block: Block = match parse2(quote! { { #body } }) {
+        Ok(block) => block,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let co = hidden_co();
+    let co_arg = quote! { mut #co: ::genawaiter::#engine::Co<_, _> };
+    if fallible {
+        YieldReplace::new().wrap_ok().visit_block_mut(&mut block);
+        let result = Ident::new("__result", Span::mixed_site());
+        let error = Ident::new("__error", Span::mixed_site());
+        quote! {
+            |#co_arg| async move {
+                let #result: ::std::result::Result<(), _> = async {
+                    #block;
+                    ::std::result::Result::Ok(())
+                }
+                .await;
+                if let ::std::result::Result::Err(#error) = #result {
+                    #co.yield_(::std::result::Result::Err(#error))
+                        .await;
+                }
+            }
+        }
+    } else {
+        YieldReplace::new().visit_block_mut(&mut block);
+        quote! { |#co_arg| async move #block }
+    }
+}
+
+/// Rewrites each `for await pat in stream { ... }` loop into a `while let` loop
+/// which awaits each item of the stream.
+fn desugar_for_await(tokens: TokenStream2) -> TokenStream2 {
+    let mut out: Vec<TokenTree> = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ref ident)
+                if ident == "for"
+                    && matches!(tokens.peek(), Some(TokenTree::Ident(next)) if next == "await") =>
+            {
+                tokens.next();
+                let label = take_label(&mut out);
+                let pat: TokenStream2 = tokens
+                    .by_ref()
+                    .take_while(
+                        |token| !matches!(token, TokenTree::Ident(i) if i == "in"),
+                    )
+                    .collect();
+                let mut stream = Vec::new();
+                let body = loop {
+                    match tokens.next() {
+                        Some(TokenTree::Group(group))
+                            if group.delimiter() == Delimiter::Brace =>
+                        {
+                            break group;
+                        }
+                        Some(token) => stream.push(token),
+                        None => abort_call_site!("expected a body after `for await`"),
+                    }
+                };
+                let stream = desugar_for_await(stream.into_iter().collect());
+                let body = desugar_group(&body);
+                let pinned = Ident::new("__stream", Span::mixed_site());
+                out.extend(quote! {
+                    match ::std::pin::pin!(#stream) {
+                        mut #pinned => {
+                            #label while let ::std::option::Option::Some(#pat) =
+                                ::std::future::poll_fn(|cx| {
+                                    ::genawaiter::__private::Stream::poll_next(
+                                        #pinned.as_mut(),
+                                        cx,
+                                    )
+                                })
+                                .await
+                            #body
+                        }
+                    }
+                });
+            }
+            TokenTree::Group(group) => {
+                out.push(TokenTree::Group(desugar_group(&group)));
+            }
+            token => out.push(token),
+        }
+    }
+    out.into_iter().collect()
+}
+
+fn desugar_group(group: &Group) -> Group {
+    let mut new = Group::new(group.delimiter(), desugar_for_await(group.stream()));
+    new.set_span(group.span());
+    new
+}
+
+/// Removes a loop label, such as `'outer:`, from the end of `out`, so it can be
+/// moved onto the loop which replaces `for await`.
+fn take_label(out: &mut Vec<TokenTree>) -> TokenStream2 {
+    match out.as_slice() {
+        [.., TokenTree::Punct(quote), TokenTree::Ident(_), TokenTree::Punct(colon)]
+            if quote.as_char() == '\'' && colon.as_char() == ':' =>
+        {
+            out.split_off(out.len() - 3).into_iter().collect()
+        }
+        _ => TokenStream2::new(),
+    }
+}
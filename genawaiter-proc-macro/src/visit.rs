@@ -1,28 +1,329 @@
-use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
 use syn::{
-    parse2,
+    punctuated::Punctuated,
+    spanned::Spanned,
     visit_mut::{self, VisitMut},
     Expr,
+    ExprAsync,
+    ExprClosure,
+    ExprMethodCall,
+    Item,
+    Macro,
+    Token,
 };
 
-pub struct YieldReplace;
+/// The error for an `.await` in an `iter_gen!`, other than on one of its yields.
+const FORBIDDEN_AWAIT: &str = "An `iter_gen!` can only await its own yields, since it \
+                               is resumed synchronously and nothing would wake any \
+                               other future. To await other futures, use `gen!` and \
+                               resume it from an async context.";
+
+/// The crate's macros which declare a generator of their own. An `.await` inside
+/// one of these belongs to that generator.
+const GENERATOR_MACROS: &[&str] = &[
+    "gen",
+    "let_gen",
+    "let_gen_using",
+    "let_send_gen",
+    "let_send_gen_using",
+    "iter_gen",
+    "stream",
+    "try_stream",
+    "producer",
+];
+
+/// The name of the `Co` argument which the macros add, when the user does not
+/// name one.
+///
+/// It is hygienic, so each generator's `Co` is distinct from any other's, even
+/// when generators are nested, and from the user's own variables.
+pub fn hidden_co() -> Ident {
+    Ident::new("__private_co_arg__", Span::mixed_site())
+}
+
+/// What encloses the current expression, within the generator's body.
+#[derive(Clone, Copy)]
+enum Scope {
+    /// The body itself, which can suspend the generator.
+    Body,
+    Closure,
+    AsyncBlock,
+}
+
+/// Rewrites each `yield_!(value)` to yield through a `Co`.
+#[derive(Clone)]
+pub struct YieldReplace {
+    co: Ident,
+    wrap_ok: bool,
+    forbid_await: bool,
+    scope: Scope,
+    /// The first `.await` which was rejected, if any.
+    rejected_await: Option<Span>,
+}
+
+impl YieldReplace {
+    /// Yields through the hidden `Co` argument which the macros add.
+    pub fn new() -> Self {
+        Self::using(hidden_co())
+    }
+
+    /// Yields through the `Co` with the given name.
+    pub fn using(co: Ident) -> Self {
+        Self {
+            co,
+            wrap_ok: false,
+            forbid_await: false,
+            scope: Scope::Body,
+            rejected_await: None,
+        }
+    }
+
+    /// Wraps each yielded value in `Ok`, for the body of a `try_stream!`.
+    pub fn wrap_ok(mut self) -> Self {
+        self.wrap_ok = true;
+        self
+    }
+
+    /// Rejects any `.await` in the body except a yield, for a generator which
+    /// is resumed synchronously, and so could never wake another future.
+    pub fn forbid_await(mut self) -> Self {
+        self.forbid_await = true;
+        self
+    }
+
+    /// Whether `call` is `co.yield_(...)`, where `co` is the generator's `Co`.
+    fn is_co_yield(&self, call: &ExprMethodCall) -> bool {
+        call.method == "yield_"
+            && match &*call.receiver {
+                Expr::Path(path) => path.path.is_ident(&self.co),
+                _ => false,
+            }
+    }
+
+    /// Whether an `.await` here would be rejected.
+    fn forbids_await(&self) -> bool {
+        self.forbid_await && matches!(self.scope, Scope::Body)
+    }
+
+    /// Finds an `.await` in the arguments of `mac` which would be rejected.
+    ///
+    /// Macro arguments are not part of the syntax tree, so they are parsed as a
+    /// list of expressions, as in `dbg!` or `vec!`, and checked on a copy. If
+    /// they do not parse, any `.await` among the tokens is rejected, except on a
+    /// yield.
+    fn find_rejected_await(&self, mac: &Macro) -> Option<Span> {
+        if is_generator_macro(mac) {
+            return None;
+        }
+        match mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            Ok(args) => {
+                args.into_iter().find_map(|mut arg| {
+                    let mut check = self.clone();
+                    check.visit_expr_mut(&mut arg);
+                    check.rejected_await
+                })
+            }
+            Err(_) => self.find_await_token(mac.tokens.clone()),
+        }
+    }
+
+    /// Finds an `.await` among `tokens` which does not follow
+    /// `co.yield_(...)`.
+    fn find_await_token(&self, tokens: TokenStream) -> Option<Span> {
+        let tokens: Vec<_> = tokens.into_iter().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => {
+                    if let Some(span) = self.find_await_token(group.stream()) {
+                        return Some(span);
+                    }
+                }
+                TokenTree::Ident(ident)
+                    if ident == "await"
+                        && i > 0
+                        && is_dot(&tokens[i - 1])
+                        && !self.ends_with_co_yield(&tokens[..i - 1]) =>
+                {
+                    return Some(ident.span());
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Whether `tokens` end with `co.yield_(...)`.
+    fn ends_with_co_yield(&self, tokens: &[TokenTree]) -> bool {
+        match tokens {
+            [.., TokenTree::Ident(co), dot, TokenTree::Ident(method), args] => {
+                *co == self.co
+                    && is_dot(dot)
+                    && method == "yield_"
+                    && matches!(args, TokenTree::Group(_))
+            }
+            _ => false,
+        }
+    }
+}
 
 impl VisitMut for YieldReplace {
     fn visit_expr_mut(&mut self, expr: &mut Expr) {
-        if let Expr::Macro(m) = expr {
-            if m.mac.path.segments.iter().any(|seg| seg.ident == "yield_") {
-                let tkns: TokenStream2 = syn::parse2(m.mac.tokens.clone())
-                    .expect("parse of TokensStream failed");
-
-                let co_call = quote! {
-                    yield_!(@__impl => __private_co_arg__, #tkns)
+        match expr {
+            Expr::Macro(m) if is_yield_macro(&m.mac) => {
+                let span = m.mac.span();
+                let message = match self.scope {
+                    Scope::Body => None,
+                    Scope::Closure => {
+                        Some(
+                            "`yield_!` cannot be used inside a closure, since the \
+                             closure cannot suspend the generator. Consider using a \
+                             loop instead.",
+                        )
+                    }
+                    Scope::AsyncBlock => {
+                        Some(
+                            "`yield_!` cannot be used inside a nested async block, \
+                             since the block cannot suspend the generator. Consider \
+                             moving the code out of the block.",
+                        )
+                    }
                 };
-                let cc: Expr = parse2(co_call).expect("parse of Expr failed");
-                *expr = cc;
+                if let Some(message) = message {
+                    *expr = error(span, message);
+                    return;
+                }
+                if self.forbid_await {
+                    if let Some(span) = self.find_rejected_await(&m.mac) {
+                        self.rejected_await.get_or_insert(span);
+                        *expr = error(span, FORBIDDEN_AWAIT);
+                        return;
+                    }
+                }
+                let value = m.mac.tokens.clone();
+                if value.is_empty() {
+                    *expr = error(
+                        span,
+                        "`yield_!` needs a value to yield. To yield nothing, write \
+                         `yield_!(())`.",
+                    );
+                    return;
+                }
+                let value = if self.wrap_ok {
+                    quote_spanned! {value.span()=> ::std::result::Result::Ok(#value) }
+                } else {
+                    quote! { #value }
+                };
+
+                // The rewritten macro keeps the span of the original, so errors
+                // point at the user's code.
+                let co = &self.co;
+                m.mac.tokens = quote_spanned! {span=> @__impl => #co, #value };
+            }
+            // Awaiting the `Co` directly is fine, so only its arguments are
+            // checked.
+            Expr::Await(await_expr) => {
+                match &mut *await_expr.base {
+                    Expr::MethodCall(call) if self.is_co_yield(call) => {
+                        for arg in &mut call.args {
+                            self.visit_expr_mut(arg);
+                        }
+                    }
+                    base if self.forbids_await() => {
+                        let span = base.span();
+                        self.rejected_await.get_or_insert(span);
+                        *expr = error(span, FORBIDDEN_AWAIT);
+                    }
+                    base => self.visit_expr_mut(base),
+                }
+            }
+            Expr::MethodCall(call) if self.is_co_yield(call) => {
+                let message = format!(
+                    "The future returned by `{co}.yield_()` must be awaited \
+                     immediately. Write `{co}.yield_(value).await`, or use \
+                     `yield_!(value)`.",
+                    co = self.co,
+                );
+                *expr = error(call.span(), &message);
+            }
+            Expr::Macro(m) if self.forbids_await() => {
+                if let Some(span) = self.find_rejected_await(&m.mac) {
+                    self.rejected_await.get_or_insert(span);
+                    *expr = error(span, FORBIDDEN_AWAIT);
+                }
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_expr_closure_mut(&mut self, closure: &mut ExprClosure) {
+        let scope = std::mem::replace(&mut self.scope, Scope::Closure);
+        visit_mut::visit_expr_closure_mut(self, closure);
+        self.scope = scope;
+    }
+
+    fn visit_expr_async_mut(&mut self, block: &mut ExprAsync) {
+        let scope = std::mem::replace(&mut self.scope, Scope::AsyncBlock);
+        visit_mut::visit_expr_async_mut(self, block);
+        self.scope = scope;
+    }
+
+    // A nested item cannot reach the generator's `Co`, so any `yield_!` inside
+    // it is left alone, and reports its own error. This also leaves alone any
+    // generator declared by the item, which is expanded on its own.
+    //
+    // The exception is a macro invoked with braces in statement position, which
+    // is parsed as an item, but runs in the generator's body.
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        if let Item::Macro(m) = item {
+            if m.ident.is_none() && self.forbids_await() {
+                if let Some(span) = self.find_rejected_await(&m.mac) {
+                    self.rejected_await.get_or_insert(span);
+                    *item = Item::Verbatim(quote_spanned! {span=>
+                        ::std::compile_error!(#FORBIDDEN_AWAIT);
+                    });
+                }
             }
         }
+    }
+}
+
+/// Reports an error at `span`, in place of an expression.
+///
+/// This is used instead of aborting, so the rest of the expansion still
+/// compiles, and the user sees only this error.
+fn error(span: Span, message: &str) -> Expr {
+    Expr::Verbatim(quote_spanned! {span=> ::std::compile_error!(#message) })
+}
 
-        visit_mut::visit_expr_mut(self, expr)
+/// Whether `mac` is one of the crate's macros which declare a generator of
+/// their own: `gen!`, or `{rc,sync,stack}::gen!` (optionally preceded by
+/// `genawaiter::`), and likewise for the rest of `GENERATOR_MACROS`.
+fn is_generator_macro(mac: &Macro) -> bool {
+    let is_engine =
+        |engine: &Ident| engine == "rc" || engine == "sync" || engine == "stack";
+    let segments: Vec<_> = mac.path.segments.iter().map(|seg| &seg.ident).collect();
+    let name = match segments.as_slice() {
+        [name] if mac.path.leading_colon.is_none() => name,
+        [engine, name] if mac.path.leading_colon.is_none() && is_engine(engine) => name,
+        [krate, engine, name] if *krate == "genawaiter" && is_engine(engine) => name,
+        _ => return false,
+    };
+    GENERATOR_MACROS.iter().any(|generator| *name == generator)
+}
+
+/// Whether `token` is a `.`.
+fn is_dot(token: &TokenTree) -> bool {
+    match token {
+        TokenTree::Punct(punct) => punct.as_char() == '.',
+        _ => false,
     }
 }
+
+/// Whether `mac` is `yield_!`, by any path.
+fn is_yield_macro(mac: &Macro) -> bool {
+    mac.path
+        .segments
+        .last()
+        .is_some_and(|seg| seg.ident == "yield_")
+}
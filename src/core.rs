@@ -5,15 +5,28 @@ use std::{
     task::{Context, Poll},
 };
 
+/// The value held in an [`Airlock`].
+///
+/// Values pass through the airlock in both directions. The consumer puts a
+/// `Resume` in before polling the producer, and the producer swaps it for a
+/// `Yield` each time it yields.
 pub enum Next<Y, R> {
+    /// The airlock holds nothing.
     Empty,
+    /// The producer yielded a value, which the consumer has not taken yet.
     Yield(Y),
+    /// The consumer resumed the generator with a value, which the producer has
+    /// not taken yet.
     Resume(R),
+    /// The generator has completed. (This is only tracked in debug builds, to
+    /// catch a `Co` which is used after its generator completes.)
     Completed,
 }
 
 #[allow(clippy::use_self)]
 impl<Y, R> Next<Y, R> {
+    /// Returns which variant this is, without the value it holds.
+    #[must_use]
     pub fn without_values(&self) -> Next<(), ()> {
         match self {
             Self::Empty => Next::Empty,
@@ -24,6 +37,16 @@ impl<Y, R> Next<Y, R> {
     }
 }
 
+/// Polls a producer's future once, and returns what it yielded, or its
+/// completion value.
+///
+/// The caller should put the resume argument in the airlock (as
+/// `Next::Resume`) first. This is the heart of every engine's `resume_with`.
+///
+/// # Panics
+///
+/// Panics if the producer awaits something other than `co.yield_`. Use
+/// [`async_advance`] for async generators.
 pub fn advance<Y, R, F: Future>(
     future: Pin<&mut F>,
     airlock: &impl Airlock<Yield = Y, Resume = R>,
@@ -59,6 +82,11 @@ pub fn advance<Y, R, F: Future>(
     }
 }
 
+/// Returns a future which polls a producer's future until it yields or
+/// completes.
+///
+/// This is the async counterpart of [`advance`]. Unlike `advance`, the
+/// producer may await other futures.
 pub fn async_advance<'a, Y, R, F: Future>(
     future: Pin<&'a mut F>,
     airlock: impl Airlock<Yield = Y, Resume = R> + 'a,
@@ -71,7 +99,7 @@ struct Advance<'a, F: Future, A: Airlock> {
     airlock: A,
 }
 
-impl<'a, F: Future, A: Airlock> Advance<'a, F, A> {
+impl<F: Future, A: Airlock> Advance<'_, F, A> {
     fn future_mut(self: Pin<&mut Self>) -> Pin<&mut F> {
         // Safety: This is just projecting a pinned reference. Neither `self` nor
         // `self.future` are moved.
@@ -79,7 +107,7 @@ impl<'a, F: Future, A: Airlock> Advance<'a, F, A> {
     }
 }
 
-impl<'a, F: Future, A: Airlock> Future for Advance<'a, F, A> {
+impl<F: Future, A: Airlock> Future for Advance<'_, F, A> {
     type Output = GeneratorState<A::Yield, F::Output>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -102,24 +130,124 @@ impl<'a, F: Future, A: Airlock> Future for Advance<'a, F, A> {
     }
 }
 
+/// The channel through which a [`Co`] passes values to and from its generator.
+///
+/// Each engine has its own airlock, and its `Co` type is an alias for `Co<A>`
+/// with that airlock filled in. This makes it possible to write a producer which
+/// works with every engine (except [`thread`](thread/index.html), whose
+/// producers are not `async`):
+///
+/// ```rust
+/// use genawaiter::{rc, stack::let_gen_using, sync, Airlock, Co};
+///
+/// async fn countdown(mut co: Co<impl Airlock<Yield = i32, Resume = ()>>) {
+///     for n in (1..=3).rev() {
+///         co.yield_(n).await;
+///     }
+/// }
+///
+/// let rc_items: Vec<_> = rc::Gen::new(countdown).into_iter().collect();
+/// let sync_items: Vec<_> = sync::Gen::new(countdown).into_iter().collect();
+/// let_gen_using!(stack_gen, countdown);
+/// let stack_items: Vec<_> = stack_gen.into_iter().collect();
+/// assert_eq!(rc_items, [3, 2, 1]);
+/// assert_eq!(sync_items, [3, 2, 1]);
+/// assert_eq!(stack_items, [3, 2, 1]);
+/// ```
+///
+/// An [`inline`](inline/index.html) generator needs a producer which accepts a
+/// `Co` of any lifetime, which a generic function cannot express. Wrap it in an
+/// `async fn` which takes an `inline::Co<'_, _>` instead.
+///
+/// Implement this trait to build your own engine. See the
+/// [`engine`](engine/index.html) module.
 pub trait Airlock {
+    /// The type of value the generator yields.
     type Yield;
+
+    /// The type of value the generator accepts as a resume argument.
     type Resume;
 
+    /// Returns which variant the airlock holds, without taking its value.
     fn peek(&self) -> Next<(), ()>;
 
+    /// Puts `next` into the airlock, and returns what it held before.
     fn replace(
         &self,
         next: Next<Self::Yield, Self::Resume>,
     ) -> Next<Self::Yield, Self::Resume>;
 }
 
+impl<A: Airlock + ?Sized> Airlock for &A {
+    type Yield = A::Yield;
+    type Resume = A::Resume;
+
+    fn peek(&self) -> Next<(), ()> {
+        (**self).peek()
+    }
+
+    fn replace(
+        &self,
+        next: Next<Self::Yield, Self::Resume>,
+    ) -> Next<Self::Yield, Self::Resume> {
+        (**self).replace(next)
+    }
+}
+
+/// A function which accepts a [`Co`] and returns the producer's future.
+///
+/// This is implemented for every such function, so it never needs to be
+/// implemented by hand. It exists so that a function can return a producer
+/// without naming the type of its future, which is how `#[producer_fn]` works
+/// for functions which take arguments.
+///
+/// ```rust
+/// use genawaiter::{rc, ProducerFn};
+/// use std::future::Future;
+///
+/// fn count_to(
+///     n: i32,
+/// ) -> impl ProducerFn<rc::Co<i32>, Future = impl Future<Output = ()>> {
+///     move |mut co: rc::Co<i32>| {
+///         async move {
+///             for i in 1..=n {
+///                 co.yield_(i).await;
+///             }
+///         }
+///     }
+/// }
+///
+/// let items: Vec<_> = rc::Gen::new(count_to(3)).into_iter().collect();
+/// assert_eq!(items, [1, 2, 3]);
+/// ```
+pub trait ProducerFn<C>: FnOnce(C) -> <Self as ProducerFn<C>>::Future {
+    /// The type of the producer's future.
+    type Future: Future;
+}
+
+impl<C, F, Fut> ProducerFn<C> for F
+where
+    F: FnOnce(C) -> Fut,
+    Fut: Future,
+{
+    type Future = Fut;
+}
+
+/// This object lets you yield values from the generator by calling the `yield_`
+/// method.
+///
+/// Each engine has an alias for this type, such as [`rc::Co`](rc/type.Co.html).
+/// Use `Co<impl Airlock>` to accept the `Co` of any engine. See [`Airlock`].
 pub struct Co<A: Airlock> {
     airlock: A,
 }
 
 impl<A: Airlock> Co<A> {
-    pub(crate) fn new(airlock: A) -> Self {
+    /// Creates a `Co` which passes values through `airlock`.
+    ///
+    /// This is only needed to build a custom engine. The airlock is typically a
+    /// handle which shares its state with the generator.
+    pub fn new(airlock: A) -> Self {
         Self { airlock }
     }
 
@@ -127,6 +255,11 @@ impl<A: Airlock> Co<A> {
     ///
     /// The caller should immediately `await` the result of this function.
     ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the previous call was not awaited, or if the
+    /// generator has already completed.
+    ///
     /// [_See the module-level docs for examples._](.)
     pub fn yield_(&mut self, value: A::Yield) -> impl Future<Output = A::Resume> + '_ {
         #[cfg(debug_assertions)]
@@ -157,13 +290,13 @@ struct Barrier<'a, A: Airlock> {
     airlock: &'a A,
 }
 
-impl<'a, A: Airlock> Future for Barrier<'a, A> {
+impl<A: Airlock> Future for Barrier<'_, A> {
     type Output = A::Resume;
 
     fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
         match self.airlock.peek() {
-            Next::Yield(_) => Poll::Pending,
-            Next::Resume(_) => {
+            Next::Yield(()) => Poll::Pending,
+            Next::Resume(()) => {
                 let next = self.airlock.replace(Next::Empty);
                 match next {
                     Next::Resume(arg) => Poll::Ready(arg),
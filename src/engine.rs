@@ -0,0 +1,234 @@
+/*!
+Building blocks for implementing a custom engine.
+
+Each engine in this crate is built from the same parts:
+
+- An [`Airlock`], which holds a [`Next`] value. The generator and its [`Co`] each have
+  a handle to it. It is how values pass between them.
+- A `Gen` type, which owns the producer's future. To resume, it puts a
+  `Next::Resume` into the airlock, and then calls [`advance`] (or [`async_advance`])
+  to poll the future until it yields or completes.
+
+Your own engine can use the same parts, for example to store the airlock somewhere
+else, or to instrument it. Here is a minimal engine which keeps its airlock in an
+`Rc<RefCell<_>>`:
+
+```rust
+use genawaiter::{
+    engine::{advance, Airlock, Next},
+    Co,
+    Coroutine,
+    GeneratorState,
+};
+use std::{cell::RefCell, future::Future, mem, pin::Pin, rc::Rc};
+
+pub struct MyAirlock<Y, R>(Rc<RefCell<Next<Y, R>>>);
+
+impl<Y, R> Airlock for MyAirlock<Y, R> {
+    type Yield = Y;
+    type Resume = R;
+
+    fn peek(&self) -> Next<(), ()> {
+        self.0.borrow().without_values()
+    }
+
+    fn replace(&self, next: Next<Y, R>) -> Next<Y, R> {
+        mem::replace(&mut self.0.borrow_mut(), next)
+    }
+}
+
+pub struct MyGen<Y, R, F: Future> {
+    airlock: MyAirlock<Y, R>,
+    future: Pin<Box<F>>,
+}
+
+impl<Y, R, F: Future> MyGen<Y, R, F> {
+    pub fn new(producer: impl FnOnce(Co<MyAirlock<Y, R>>) -> F) -> Self {
+        let next = Rc::new(RefCell::new(Next::Empty));
+        let future = Box::pin(producer(Co::new(MyAirlock(next.clone()))));
+        Self { airlock: MyAirlock(next), future }
+    }
+}
+
+impl<Y, R, F: Future> Coroutine for MyGen<Y, R, F> {
+    type Yield = Y;
+    type Resume = R;
+    type Return = F::Output;
+
+    fn resume_with(mut self: Pin<&mut Self>, arg: R) -> GeneratorState<Y, F::Output> {
+        let this = &mut *self;
+        this.airlock.replace(Next::Resume(arg));
+        advance(this.future.as_mut(), &this.airlock)
+    }
+}
+
+# let mut gen = MyGen::new(|mut co| async move { co.yield_(10).await; });
+# assert_eq!(Pin::new(&mut gen).resume_with(()), GeneratorState::Yielded(10));
+```
+
+# Conformance tests
+
+The [`engine_conformance_tests!`](../macro.engine_conformance_tests.html) macro
+generates a suite of tests which check that an engine follows the same protocol as the
+built-in engines. Pass it the path of your generator's constructor, which must accept
+a producer of type `impl FnOnce(Co<A>) -> impl Future` (like `rc::Gen::new`), and
+return a `Coroutine`.
+
+```ignore
+mod conformance {
+    genawaiter::engine_conformance_tests!(my_engine::MyGen::new);
+}
+```
+*/
+
+pub use crate::core::{advance, async_advance, Airlock, Co, Next};
+
+/// Generates tests which check that an engine follows the generator protocol.
+///
+/// The argument is the path of a function which creates a generator from a
+/// producer, such as `genawaiter::rc::Gen::new`. The generator must implement
+/// [`Coroutine`](ops/trait.Coroutine.html).
+///
+/// The tests check that:
+///
+/// - Values are yielded in order, and then the generator completes.
+/// - The completion value is returned.
+/// - Each resume argument is returned from the `co.yield_` that is being
+///   awaited, and the first one (which has no `co.yield_` to return from) is
+///   discarded.
+/// - A producer can complete without yielding anything.
+/// - Dropping an unfinished generator drops its producer.
+///
+/// Invoke it in its own module, since it defines several test functions.
+///
+/// # Example
+///
+/// ```ignore
+/// mod rc_conformance {
+///     genawaiter::engine_conformance_tests!(genawaiter::rc::Gen::new);
+/// }
+/// ```
+#[macro_export]
+macro_rules! engine_conformance_tests {
+    ($new:path $(,)?) => {
+        #[test]
+        fn yields_in_order() {
+            async fn producer(
+                mut co: $crate::Co<impl $crate::Airlock<Yield = i32, Resume = ()>>,
+            ) {
+                for n in (1..).step_by(2).take_while(|&n| n < 10) {
+                    co.yield_(n).await;
+                }
+            }
+
+            let mut gen = ::std::boxed::Box::pin($new(producer));
+            let mut items = ::std::vec::Vec::new();
+            loop {
+                match $crate::Coroutine::resume_with(gen.as_mut(), ()) {
+                    $crate::GeneratorState::Yielded(n) => items.push(n),
+                    $crate::GeneratorState::Complete(()) => break,
+                }
+            }
+            assert_eq!(items, [1, 3, 5, 7, 9]);
+        }
+
+        #[test]
+        fn returns_completion_value() {
+            async fn producer(
+                mut co: $crate::Co<impl $crate::Airlock<Yield = i32, Resume = ()>>,
+            ) -> &'static str {
+                co.yield_(10).await;
+                "done"
+            }
+
+            let mut gen = ::std::boxed::Box::pin($new(producer));
+            assert_eq!(
+                $crate::Coroutine::resume_with(gen.as_mut(), ()),
+                $crate::GeneratorState::Yielded(10),
+            );
+            assert_eq!(
+                $crate::Coroutine::resume_with(gen.as_mut(), ()),
+                $crate::GeneratorState::Complete("done"),
+            );
+        }
+
+        #[test]
+        fn passes_resume_args() {
+            async fn producer(
+                mut co: $crate::Co<
+                    impl $crate::Airlock<Yield = i32, Resume = &'static str>,
+                >,
+            ) -> (&'static str, &'static str) {
+                let first = co.yield_(1).await;
+                let second = co.yield_(2).await;
+                (first, second)
+            }
+
+            let mut gen = ::std::boxed::Box::pin($new(producer));
+            assert_eq!(
+                $crate::Coroutine::resume_with(gen.as_mut(), "ignored"),
+                $crate::GeneratorState::Yielded(1),
+            );
+            assert_eq!(
+                $crate::Coroutine::resume_with(gen.as_mut(), "abc"),
+                $crate::GeneratorState::Yielded(2),
+            );
+            assert_eq!(
+                $crate::Coroutine::resume_with(gen.as_mut(), "def"),
+                $crate::GeneratorState::Complete(("abc", "def")),
+            );
+        }
+
+        #[test]
+        fn completes_without_yielding() {
+            async fn producer(
+                _co: $crate::Co<impl $crate::Airlock<Yield = i32, Resume = ()>>,
+            ) -> i32 {
+                5
+            }
+
+            let mut gen = ::std::boxed::Box::pin($new(producer));
+            assert_eq!(
+                $crate::Coroutine::resume_with(gen.as_mut(), ()),
+                $crate::GeneratorState::Complete(5),
+            );
+        }
+
+        #[test]
+        fn drops_unfinished_producer() {
+            use ::std::sync::{
+                atomic::{AtomicBool, Ordering},
+                Arc,
+            };
+
+            struct SetFlagOnDrop(Arc<AtomicBool>);
+
+            impl Drop for SetFlagOnDrop {
+                fn drop(&mut self) {
+                    self.0.store(true, Ordering::SeqCst);
+                }
+            }
+
+            async fn producer(
+                flag: Arc<AtomicBool>,
+                mut co: $crate::Co<impl $crate::Airlock<Yield = i32, Resume = ()>>,
+            ) {
+                let _set_on_drop = SetFlagOnDrop(flag);
+                co.yield_(10).await;
+                co.yield_(20).await;
+            }
+
+            let flag = Arc::new(AtomicBool::new(false));
+            let capture_the_flag = flag.clone();
+            let mut gen =
+                ::std::boxed::Box::pin($new(move |co| producer(capture_the_flag, co)));
+            assert_eq!(
+                $crate::Coroutine::resume_with(gen.as_mut(), ()),
+                $crate::GeneratorState::Yielded(10),
+            );
+            assert!(!flag.load(Ordering::SeqCst));
+            drop(gen);
+            assert!(flag.load(Ordering::SeqCst));
+        }
+    };
+}
@@ -0,0 +1,125 @@
+use crate::{core, core::Next};
+use std::{cell::UnsafeCell, future::Future, marker::PhantomData, ptr, ptr::NonNull};
+
+/// This type holds the value that is pending being returned from the generator.
+///
+/// # Safety
+///
+/// This type is `!Sync` (so, single-thread), never exposed to user-land code,
+/// and never borrowed across a function call, so safety can be verified locally
+/// at each use site.
+pub struct Airlock<Y, R>(UnsafeCell<Next<Y, R>>);
+
+impl<Y, R> Default for Airlock<Y, R> {
+    fn default() -> Self {
+        Self(UnsafeCell::new(Next::Empty))
+    }
+}
+
+impl<Y, R> core::Airlock for Airlock<Y, R> {
+    type Yield = Y;
+    type Resume = R;
+
+    fn peek(&self) -> Next<(), ()> {
+        // Safety: This follows the safety rules above.
+        let inner = unsafe { &*self.0.get() };
+        inner.without_values()
+    }
+
+    fn replace(
+        &self,
+        next: Next<Self::Yield, Self::Resume>,
+    ) -> Next<Self::Yield, Self::Resume> {
+        // Safety: This follows the safety rules above.
+        unsafe { ptr::replace(self.0.get(), next) }
+    }
+}
+
+/// A handle to the `Airlock` inside a pinned `Gen`.
+///
+/// This is a pointer rather than a reference, so that `'y` can be any lifetime
+/// without requiring `Y: 'y` or `R: 'y`.
+///
+/// # Safety
+///
+/// A handle is only created by `Gen` once it has been pinned, and only handed to
+/// a producer which is generic over `'y`, and whose output does not depend on
+/// `'y` (see [`Producer`]). So the producer cannot move its `Co` anywhere that
+/// outlives the future it returns, which is stored in the same `Gen`, and
+/// dropped before the airlock.
+pub struct AirlockRef<'y, Y, R> {
+    airlock: NonNull<Airlock<Y, R>>,
+    lifetime: PhantomData<&'y ()>,
+}
+
+impl<Y, R> AirlockRef<'_, Y, R> {
+    /// # Safety
+    ///
+    /// `airlock` must stay in place, and outlive every use of the handle.
+    pub(crate) unsafe fn new(airlock: &Airlock<Y, R>) -> Self {
+        Self {
+            airlock: NonNull::from(airlock),
+            lifetime: PhantomData,
+        }
+    }
+
+    fn get(&self) -> &Airlock<Y, R> {
+        // Safety: This follows the safety rules above.
+        unsafe { self.airlock.as_ref() }
+    }
+}
+
+impl<Y, R> core::Airlock for AirlockRef<'_, Y, R> {
+    type Yield = Y;
+    type Resume = R;
+
+    fn peek(&self) -> Next<(), ()> {
+        self.get().peek()
+    }
+
+    fn replace(
+        &self,
+        next: Next<Self::Yield, Self::Resume>,
+    ) -> Next<Self::Yield, Self::Resume> {
+        self.get().replace(next)
+    }
+}
+
+/// This object lets you yield values from the generator by calling the `yield_`
+/// method.
+///
+/// "Co" can stand for either _controller_ or _coroutine_, depending on how
+/// theoretical you are feeling.
+///
+/// [_See the module-level docs for examples._](.)
+pub type Co<'y, Y, R = ()> = core::Co<AirlockRef<'y, Y, R>>;
+
+/// A function which can produce the values of a [`Gen`](super::Gen).
+///
+/// This is implemented for every function which accepts a [`Co`] and returns a
+/// future, such as an `async fn`. `Gen::new` requires the function to accept a
+/// `Co` with _any_ lifetime, and to complete with the same `Output` for every
+/// lifetime. This is what keeps the `Co` from escaping the generator.
+pub trait Producer<'y, Y, R> {
+    /// The completion value of the future.
+    type Output;
+
+    /// The future returned by the function.
+    type Future: Future<Output = Self::Output>;
+
+    /// Calls the function.
+    fn call(self, co: Co<'y, Y, R>) -> Self::Future;
+}
+
+impl<'y, Y, R, P, F> Producer<'y, Y, R> for P
+where
+    P: FnOnce(Co<'y, Y, R>) -> F,
+    F: Future,
+{
+    type Output = F::Output;
+    type Future = F;
+
+    fn call(self, co: Co<'y, Y, R>) -> F {
+        self(co)
+    }
+}
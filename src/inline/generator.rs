@@ -0,0 +1,194 @@
+use crate::{
+    core::{advance, async_advance, Airlock as _, Next},
+    inline::engine::{Airlock, AirlockRef, Co, Producer},
+    ops::{AsyncCoroutine, BoxCoroutine, Coroutine, GeneratorState},
+};
+use std::{
+    future::Future,
+    marker::PhantomPinned,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The future of the producer `P`.
+type ProducerFuture<Y, R, P> = <P as Producer<'static, Y, R>>::Future;
+
+/// The pinned future of a started generator, and a handle to its airlock.
+type Started<'a, Y, R, P> =
+    (Pin<&'a mut ProducerFuture<Y, R, P>>, AirlockRef<'a, Y, R>);
+
+/// This is a generator which stores its state inline, in the generator itself.
+///
+/// It can be moved freely until it is first resumed, and from then on, it must be
+/// pinned, e.g., with [`std::pin::pin!`] or [`Box::pin`].
+///
+/// [_See the module-level docs for examples._](.)
+pub struct Gen<Y, R, P: Producer<'static, Y, R>> {
+    // This is declared before `airlock`, so the future (and its `Co`) is dropped
+    // before the airlock.
+    state: State<P, ProducerFuture<Y, R, P>>,
+    airlock: Airlock<Y, R>,
+    _pinned: PhantomPinned,
+}
+
+enum State<P, F> {
+    Unstarted(P),
+    Started(F),
+    Starting,
+}
+
+impl<Y, R, P, C> Gen<Y, R, P>
+where
+    P: for<'y> Producer<'y, Y, R, Output = C>,
+{
+    /// Creates a new generator from a function.
+    ///
+    /// The function accepts a [`Co`] object, and returns a future. It is not
+    /// called until the generator is first resumed. Every time the generator is
+    /// resumed, the future is polled. Each time the future is polled, it should
+    /// do one of two things:
+    ///
+    /// - Call `co.yield_()`, and then return `Poll::Pending`.
+    /// - Drop the `Co`, and then return `Poll::Ready`.
+    ///
+    /// The function must accept a `Co` with any lifetime. An `async fn` does this
+    /// automatically, and on Rust 1.85 or later, so does an async closure whose
+    /// argument is annotated, e.g., `async |co: Co<'_, i32>| { ... }`. Its
+    /// completion value must not borrow from the `Co`.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn new(producer: P) -> Self {
+        Self {
+            state: State::Unstarted(producer),
+            airlock: Airlock::default(),
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Calls the producer if it has not been called yet, and returns its future
+    /// along with a handle to the airlock.
+    fn start(self: Pin<&mut Self>) -> Started<'_, Y, R, P> {
+        // Safety: Nothing is moved out of `self`. The future is only written into
+        // `state` before it has ever been pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        if let State::Unstarted(_) = this.state {
+            let producer = match mem::replace(&mut this.state, State::Starting) {
+                State::Unstarted(producer) => producer,
+                State::Started(_) | State::Starting => unreachable!(),
+            };
+            // Safety: `self` is pinned, so the airlock stays in place until it is
+            // dropped. `P` accepts a `Co` with any lifetime, so it cannot move it
+            // anywhere that outlives the future.
+            let co = Co::new(unsafe { AirlockRef::new(&this.airlock) });
+            this.state = State::Started(producer.call(co));
+        }
+
+        let future = match &mut this.state {
+            State::Started(future) => future,
+            State::Unstarted(_) | State::Starting => unreachable!(),
+        };
+        // Safety: The future is pinned structurally. It is never moved until it
+        // is dropped in place with the rest of `self`.
+        let future = unsafe { Pin::new_unchecked(future) };
+        // Safety: The airlock is pinned along with `self`.
+        let airlock = unsafe { AirlockRef::new(&this.airlock) };
+        (future, airlock)
+    }
+
+    /// Resumes execution of the generator.
+    ///
+    /// `arg` is the resume argument. If the generator was previously paused by
+    /// awaiting a future returned from `co.yield()`, that future will complete,
+    /// and return `arg`.
+    ///
+    /// If the generator yields a value, `Yielded` is returned. Otherwise,
+    /// `Completed` is returned.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(self: Pin<&mut Self>, arg: R) -> GeneratorState<Y, C> {
+        let (future, airlock) = self.start();
+        airlock.replace(Next::Resume(arg));
+        advance(future, &airlock)
+    }
+
+    /// Boxes this generator, so it can be stored alongside generators of other
+    /// types, as a [`BoxCoroutine`].
+    ///
+    /// [_See the `DynCoroutine` docs for examples._](../ops/trait.DynCoroutine.html)
+    #[must_use]
+    pub fn boxed<'a>(self) -> BoxCoroutine<'a, Y, R, C>
+    where
+        Self: 'a,
+    {
+        Box::pin(self)
+    }
+}
+
+impl<Y, P, C> Gen<Y, (), P>
+where
+    P: for<'y> Producer<'y, Y, (), Output = C>,
+{
+    /// Resumes execution of the generator.
+    ///
+    /// If the generator yields a value, `Yielded` is returned. Otherwise,
+    /// `Completed` is returned.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    #[allow(clippy::must_use_candidate)]
+    pub fn resume(self: Pin<&mut Self>) -> GeneratorState<Y, C> {
+        self.resume_with(())
+    }
+
+    /// Resumes execution of the generator.
+    ///
+    /// If the generator pauses without yielding, `Poll::Pending` is returned.
+    /// If the generator yields a value, `Poll::Ready(Yielded)` is returned.
+    /// Otherwise, `Poll::Ready(Completed)` is returned.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn async_resume(
+        self: Pin<&mut Self>,
+    ) -> impl Future<Output = GeneratorState<Y, C>> + '_ {
+        let (future, airlock) = self.start();
+        airlock.replace(Next::Resume(()));
+        async_advance(future, airlock)
+    }
+}
+
+impl<Y, R, P, C> Coroutine for Gen<Y, R, P>
+where
+    P: for<'y> Producer<'y, Y, R, Output = C>,
+{
+    type Yield = Y;
+    type Resume = R;
+    type Return = C;
+
+    fn resume_with(
+        self: Pin<&mut Self>,
+        arg: R,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume_with(self, arg)
+    }
+}
+
+impl<Y, R, P, C> AsyncCoroutine for Gen<Y, R, P>
+where
+    P: for<'y> Producer<'y, Y, R, Output = C>,
+{
+    type Yield = Y;
+    type Resume = R;
+    type Return = C;
+
+    fn poll_resume(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        arg: R,
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        let (future, airlock) = self.start();
+        airlock.replace(Next::Resume(arg));
+        let fut = async_advance(future, airlock);
+        pin_mut!(fut);
+        fut.poll(cx)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    inline::{Gen, Producer},
+    ops::GeneratorState,
+};
+use std::pin::Pin;
+
+impl<Y, P> Iterator for Pin<&mut Gen<Y, (), P>>
+where
+    P: for<'y> Producer<'y, Y, (), Output = ()>,
+{
+    type Item = Y;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.as_mut().resume() {
+            GeneratorState::Yielded(x) => Some(x),
+            GeneratorState::Complete(()) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inline::{Co, Gen};
+    use std::pin::pin;
+
+    async fn produce(mut co: Co<'_, i32>) {
+        co.yield_(10).await;
+        co.yield_(20).await;
+    }
+
+    #[test]
+    fn collect() {
+        let gen = pin!(Gen::new(produce));
+        let items: Vec<_> = gen.collect();
+        assert_eq!(items, [10, 20]);
+    }
+
+    #[test]
+    fn for_loop() {
+        let mut sum = 0;
+        for x in pin!(Gen::new(produce)) {
+            sum += x;
+        }
+        assert_eq!(sum, 30);
+    }
+}
@@ -0,0 +1,309 @@
+/*!
+This module implements a generator which stores its state inline, without allocating
+and without borrowing a separate `Shelf`.
+
+A [`Gen`] can be moved freely, and returned from a function, until it is first
+resumed. From then on, it must stay in place, which is enforced by resuming it
+through a `Pin`. Pin it on the stack with [`std::pin::pin!`], or on the heap with
+[`Box::pin`].
+
+```rust
+use genawaiter::inline::{Co, Gen};
+use std::pin::pin;
+
+async fn my_producer(mut co: Co<'_, u8>) {
+    co.yield_(10).await;
+}
+let mut my_generator = pin!(Gen::new(my_producer));
+# my_generator.as_mut().resume();
+```
+
+The producer must accept a [`Co`] with any lifetime, and must not return it. This is
+what prevents the `Co` from outliving the generator. An `async fn` qualifies
+automatically, and can take the rest of its state from another `async fn`:
+
+```rust
+use genawaiter::{
+    inline::{Co, Gen},
+    GeneratorState,
+};
+use std::pin::pin;
+
+async fn count_down(start: i32, mut co: Co<'_, i32>) {
+    for n in (1..=start).rev() {
+        co.yield_(n).await;
+    }
+}
+
+async fn from_three(co: Co<'_, i32>) {
+    count_down(3, co).await;
+}
+
+let mut gen = pin!(Gen::new(from_three));
+assert_eq!(gen.as_mut().resume(), GeneratorState::Yielded(3));
+```
+
+On Rust 1.85 or later, an async closure qualifies too, if its argument type is
+spelled out:
+
+```ignore
+let start = 3;
+let mut gen = pin!(Gen::new(async move |mut co: Co<'_, i32>| {
+    for n in (1..=start).rev() {
+        co.yield_(n).await;
+    }
+}));
+assert_eq!(gen.as_mut().resume(), GeneratorState::Yielded(3));
+```
+
+# Examples
+
+## Returning a generator from a function
+
+```rust
+use genawaiter::{
+    inline::{Co, Gen},
+    Coroutine,
+    GeneratorState,
+};
+use std::pin::pin;
+
+async fn odds(mut co: Co<'_, i32>) {
+    for n in (1..).step_by(2).take_while(|&n| n < 10) {
+        co.yield_(n).await;
+    }
+}
+
+fn odds_under_ten() -> impl Coroutine<Yield = i32, Resume = (), Return = ()> {
+    Gen::new(odds)
+}
+
+let mut gen = pin!(odds_under_ten());
+assert_eq!(gen.as_mut().resume_with(()), GeneratorState::Yielded(1));
+assert_eq!(gen.as_mut().resume_with(()), GeneratorState::Yielded(3));
+```
+
+## Using `Iterator`
+
+A pinned generator implements `Iterator`, so you can use it in a for loop:
+
+```rust
+# use genawaiter::inline::{Co, Gen};
+# use std::pin::pin;
+#
+# async fn odds(mut co: Co<'_, i32>) {
+#     for n in (1..).step_by(2).take_while(|&n| n < 10) {
+#         co.yield_(n).await;
+#     }
+# }
+#
+# let mut test = Vec::new();
+for num in pin!(Gen::new(odds)) {
+    println!("{}", num);
+    # test.push(num);
+}
+# assert_eq!(test, [1, 3, 5, 7, 9]);
+```
+
+## Storing a generator on the heap
+
+```rust
+# use genawaiter::{inline::{Co, Gen}, GeneratorState};
+#
+async fn produce(mut co: Co<'_, i32>) -> &'static str {
+    co.yield_(10).await;
+    "done"
+}
+
+let mut gen = Box::pin(Gen::new(produce));
+assert_eq!(gen.as_mut().resume(), GeneratorState::Yielded(10));
+assert_eq!(gen.as_mut().resume(), GeneratorState::Complete("done"));
+```
+*/
+
+pub use crate::inline::{
+    engine::{Co, Producer},
+    generator::Gen,
+};
+
+mod engine;
+mod generator;
+mod iterator;
+#[cfg(feature = "nightly")]
+mod nightly;
+#[cfg(feature = "futures03")]
+mod stream;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inline::{Co, Gen},
+        testing::SlowFuture,
+        Coroutine,
+        GeneratorState,
+    };
+    use futures::executor::block_on;
+    use std::{
+        cell::{Cell, RefCell},
+        future,
+        pin::pin,
+    };
+
+    async fn simple_producer(mut co: Co<'_, i32>) -> &'static str {
+        co.yield_(10).await;
+        "done"
+    }
+
+    #[test]
+    fn function() {
+        let mut gen = pin!(Gen::new(simple_producer));
+        assert_eq!(gen.as_mut().resume(), GeneratorState::Yielded(10));
+        assert_eq!(gen.as_mut().resume(), GeneratorState::Complete("done"));
+    }
+
+    #[test]
+    fn movable_until_started() {
+        fn make() -> impl Coroutine<Yield = i32, Resume = (), Return = &'static str> {
+            Gen::new(simple_producer)
+        }
+
+        let gen = make();
+        let moved = Box::new(gen);
+        let mut gen = Box::into_pin(moved);
+        assert_eq!(gen.as_mut().resume_with(()), GeneratorState::Yielded(10));
+    }
+
+    #[test]
+    fn lazy_start() {
+        thread_local! {
+            static STARTED: Cell<bool> = const { Cell::new(false) };
+        }
+
+        async fn produce(mut co: Co<'_, ()>) {
+            STARTED.with(|started| started.set(true));
+            co.yield_(()).await;
+        }
+
+        let gen = Gen::new(produce);
+        assert!(!STARTED.with(Cell::get));
+        let mut gen = pin!(gen);
+        gen.as_mut().resume();
+        assert!(STARTED.with(Cell::get));
+    }
+
+    #[test]
+    fn resume_args() {
+        thread_local! {
+            static RESUMES: RefCell<Vec<&'static str>> =
+                const { RefCell::new(Vec::new()) };
+        }
+
+        fn resumes() -> Vec<&'static str> {
+            RESUMES.with(|resumes| resumes.borrow().clone())
+        }
+
+        async fn gen(mut co: Co<'_, i32, &'static str>) {
+            let resume_arg = co.yield_(10).await;
+            RESUMES.with(|resumes| resumes.borrow_mut().push(resume_arg));
+            let resume_arg = co.yield_(20).await;
+            RESUMES.with(|resumes| resumes.borrow_mut().push(resume_arg));
+        }
+
+        let mut gen = pin!(Gen::new(gen));
+        assert_eq!(resumes(), &[] as &[&str]);
+
+        assert_eq!(
+            gen.as_mut().resume_with("ignored"),
+            GeneratorState::Yielded(10)
+        );
+        assert_eq!(resumes(), &[] as &[&str]);
+
+        assert_eq!(gen.as_mut().resume_with("abc"), GeneratorState::Yielded(20));
+        assert_eq!(resumes(), &["abc"]);
+
+        assert_eq!(
+            gen.as_mut().resume_with("def"),
+            GeneratorState::Complete(())
+        );
+        assert_eq!(resumes(), &["abc", "def"]);
+    }
+
+    #[test]
+    fn coroutine() {
+        fn first<C: Coroutine<Resume = ()>>(
+            gen: C,
+        ) -> GeneratorState<C::Yield, C::Return> {
+            let gen = pin!(gen);
+            gen.resume_with(())
+        }
+
+        assert_eq!(
+            first(Gen::new(simple_producer)),
+            GeneratorState::Yielded(10)
+        );
+    }
+
+    #[test]
+    fn async_resume() {
+        async fn produce(mut co: Co<'_, i32>) {
+            SlowFuture::new().await;
+            co.yield_(10).await;
+            SlowFuture::new().await;
+            co.yield_(20).await;
+        }
+
+        block_on(async {
+            let mut gen = pin!(Gen::new(produce));
+            assert_eq!(
+                gen.as_mut().async_resume().await,
+                GeneratorState::Yielded(10)
+            );
+            assert_eq!(
+                gen.as_mut().async_resume().await,
+                GeneratorState::Yielded(20)
+            );
+            assert_eq!(
+                gen.as_mut().async_resume().await,
+                GeneratorState::Complete(())
+            );
+        });
+    }
+
+    /// Dropping a generator which was never resumed drops the producer, and
+    /// dropping one partway through drops the future.
+    #[test]
+    fn drop() {
+        thread_local! {
+            static DROPPED: Cell<bool> = const { Cell::new(false) };
+        }
+
+        struct SetOnDrop;
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                DROPPED.with(|dropped| dropped.set(true));
+            }
+        }
+
+        async fn produce(mut co: Co<'_, ()>) {
+            let _guard = SetOnDrop;
+            co.yield_(()).await;
+        }
+
+        let guard = SetOnDrop;
+        let gen = Gen::new(move |_: Co<'_, ()>| {
+            let _guard = guard;
+            future::ready(())
+        });
+        std::mem::drop(gen);
+        assert!(DROPPED.with(Cell::get));
+
+        DROPPED.with(|dropped| dropped.set(false));
+        {
+            let mut gen = pin!(Gen::new(produce));
+            gen.as_mut().resume();
+            assert!(!DROPPED.with(Cell::get));
+        }
+        assert!(DROPPED.with(Cell::get));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    inline::{Gen, Producer},
+    ops::{AsyncCoroutine, Coroutine, GeneratorState},
+};
+use std::{
+    async_iter::AsyncIterator,
+    ops::{self, CoroutineState},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl<Y, R, P, C> ops::Coroutine<R> for Gen<Y, R, P>
+where
+    P: for<'y> Producer<'y, Y, R, Output = C>,
+{
+    type Yield = Y;
+    type Return = C;
+
+    fn resume(self: Pin<&mut Self>, arg: R) -> CoroutineState<Y, C> {
+        Coroutine::resume_with(self, arg).into()
+    }
+}
+
+impl<Y, P> AsyncIterator for Gen<Y, (), P>
+where
+    P: for<'y> Producer<'y, Y, (), Output = ()>,
+{
+    type Item = Y;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        match self.poll_resume(cx, ()) {
+            Poll::Ready(GeneratorState::Yielded(x)) => Poll::Ready(Some(x)),
+            Poll::Ready(GeneratorState::Complete(())) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inline::{Co, Gen},
+        testing::SlowFuture,
+    };
+    use std::{
+        async_iter::AsyncIterator,
+        future,
+        ops::{Coroutine, CoroutineState},
+        pin::pin,
+    };
+
+    #[test]
+    fn std_coroutine() {
+        async fn produce(mut co: Co<'_, i32, i32>) -> &'static str {
+            let n = co.yield_(10).await;
+            co.yield_(n + 1).await;
+            "done"
+        }
+
+        let mut gen = pin!(Gen::new(produce));
+        assert_eq!(gen.as_mut().resume(0), CoroutineState::Yielded(10));
+        assert_eq!(gen.as_mut().resume(5), CoroutineState::Yielded(6));
+        assert_eq!(gen.as_mut().resume(0), CoroutineState::Complete("done"));
+    }
+
+    #[test]
+    fn async_iterator() {
+        async fn produce(mut co: Co<'_, i32>) {
+            SlowFuture::new().await;
+            co.yield_(10).await;
+        }
+
+        let mut gen = pin!(Gen::new(produce));
+        futures::executor::block_on(async {
+            let item = future::poll_fn(|cx| gen.as_mut().poll_next(cx)).await;
+            assert_eq!(item, Some(10));
+            let item = future::poll_fn(|cx| gen.as_mut().poll_next(cx)).await;
+            assert_eq!(item, None);
+        });
+    }
+}
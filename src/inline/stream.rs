@@ -0,0 +1,50 @@
+use crate::{
+    inline::{Gen, Producer},
+    ops::{AsyncCoroutine, GeneratorState},
+};
+use futures_core::{
+    task::{Context, Poll},
+    Stream,
+};
+use std::pin::Pin;
+
+impl<Y, P> Stream for Gen<Y, (), P>
+where
+    P: for<'y> Producer<'y, Y, (), Output = ()>,
+{
+    type Item = Y;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        match self.poll_resume(cx, ()) {
+            Poll::Ready(GeneratorState::Yielded(x)) => Poll::Ready(Some(x)),
+            Poll::Ready(GeneratorState::Complete(())) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inline::{Co, Gen},
+        testing::SlowFuture,
+    };
+    use futures::executor::block_on_stream;
+
+    #[test]
+    fn non_blocking() {
+        async fn produce(mut co: Co<'_, i32>) {
+            SlowFuture::new().await;
+            co.yield_(10).await;
+            SlowFuture::new().await;
+            co.yield_(20).await;
+        }
+
+        let gen = Box::pin(Gen::new(produce));
+        let items: Vec<_> = block_on_stream(gen).collect();
+        assert_eq!(items, [10, 20]);
+    }
+}
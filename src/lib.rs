@@ -15,29 +15,41 @@ This crate has these features:
 
 - `futures03` (disabled by default) – Implements `Stream` for all generator types.
   Adds a dependency on `futures-core`.
+- `nightly` (disabled by default) – Bridges to the stdlib's unstable coroutine types.
+  Requires a nightly compiler. See [below](#backported-stdlib-types).
 - `proc_macro` (enabled by default) – Adds support for macros, and adds various
   compile-time dependencies.
 
 # Choose your guarantees
 
-This crate supplies three concrete implementations of generators:
+This crate supplies five concrete implementations of generators:
 
 1. [`genawaiter::stack`](stack) – Allocation-free. You should prefer this when possible.
 
-2. [`genawaiter::rc`](rc) – This allocates.
+2. [`genawaiter::inline`](inline) – Allocation-free, and can be returned from a
+   function, but must be pinned before it is resumed.
 
-3. [`genawaiter::sync`](sync) – This allocates, and can be shared between threads.
+3. [`genawaiter::rc`](rc) – This allocates.
+
+4. [`genawaiter::sync`](sync) – This allocates, and can be shared between threads.
+
+5. [`genawaiter::thread`](thread) – This spawns a thread, and the producer is an
+   ordinary blocking function instead of an `async` one.
 
    [unus]: https://github.com/whatisaphone/genawaiter/blob/4a2b185/src/waker.rs#L9
    [duo]: https://github.com/whatisaphone/genawaiter/blob/4a2b185/src/rc/engine.rs#L26
 
+If none of these fit, you can build your own engine from the same parts. See the
+[`engine`] module.
+
 Here are the differences in table form:
 
-|                                       | [`stack::Gen`] | [`rc::Gen`] | [`sync::Gen`] |
-|---------------------------------------|----------------|-------------|---------------|
-| Allocations per generator            | 0               | 2           | 2             |
-| Generator can be moved after created | no              | yes         | yes           |
-| Thread-safe                          | no              | no          | yes           |
+|                                       | [`stack::Gen`] | [`inline::Gen`]    | [`rc::Gen`] | [`sync::Gen`] | [`thread::Gen`] |
+|---------------------------------------|----------------|--------------------|-------------|---------------|-----------------|
+| Allocations per generator            | 0               | 0                  | 2           | 2             | 1 thread        |
+| Generator can be moved after created | no              | until first resume | yes         | yes           | yes             |
+| Thread-safe                          | opt-in          | no                 | no          | yes           | yes             |
+| Producer is `async`                  | yes             | yes                | yes         | yes           | no              |
 
 # Creating a generator
 
@@ -88,6 +100,9 @@ let count_to_ten = Gen::new(count_producer);
 # }
 ```
 
+A producer can also be written once for every engine, by accepting a
+`Co<impl Airlock>` (see [`Airlock`]), or with the [`producer_fn`] attribute.
+
 If neither of these offers enough control for you, you can always skip the macros and
 use the low-level API directly:
 
@@ -190,6 +205,61 @@ assert_eq!(generator.resume(), GeneratorState::Complete("done"));
 # }
 ```
 
+## Declaring types
+
+The yield, resume, and completion types are usually inferred, but you can also declare
+them before the body, with `yield`, `resume`, and `return`. Each one is optional. This
+helps when there is nothing to infer a type from, and it gives clearer errors when the
+body does not match.
+
+```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
+# use genawaiter::{sync::gen, yield_, GeneratorState};
+#
+let mut printer = gen!(yield (), resume &str, return usize => {
+    let mut count = 0;
+    loop {
+        let string = yield_!(());
+        if string.is_empty() {
+            return count;
+        }
+        println!("{}", string);
+        count += 1;
+    }
+});
+printer.resume_with("ignored");
+printer.resume_with("hello");
+assert_eq!(printer.resume_with(""), GeneratorState::Complete(1));
+# }
+```
+
+## Passing the `Co` to a helper
+
+The macros usually keep the [`Co`](sync::Co) object to themselves. To share logic
+with an `async fn` which accepts a `Co`, name it, like a closure argument. `yield_!`
+still works alongside it.
+
+```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
+# use genawaiter::{sync::{gen, Co}, yield_};
+#
+async fn countdown(co: &mut Co<i32>, from: i32) {
+    for n in (1..=from).rev() {
+        co.yield_(n).await;
+    }
+}
+
+let generator = gen!(|co| {
+    countdown(&mut co, 2).await;
+    yield_!(0);
+});
+let xs: Vec<_> = generator.into_iter().collect();
+assert_eq!(xs, [2, 1, 0]);
+# }
+```
+
 # Async generators
 
 If you await other futures inside the generator, it becomes an _async generator_. It
@@ -243,6 +313,12 @@ match gen.async_resume().await {
 # }
 ```
 
+# Combining generators
+
+The [`ops`] module has functions which combine several generators into one, such as
+[`ops::zip`], [`ops::interleave`], and [`ops::merge_by`]. They work with generators
+from any engine.
+
 # Backported stdlib types
 
 This crate supplies [`Generator`](trait.Generator.html) and
@@ -254,9 +330,18 @@ developers might recognize this as a polyfill.
 There is also a [`Coroutine`](trait.Coroutine.html) trait, which does not come from the
 stdlib. A `Coroutine` is a generalization of a `Generator`. A `Generator` constrains the
 resume argument type to `()`, but in a `Coroutine` it can be anything.
+
+On nightly Rust, the `nightly` feature lets the two worlds interoperate, so you can
+migrate gradually:
+
+- Every engine's `Gen` implements the stdlib's `Coroutine` trait, and the async
+  generators (of every engine except [`thread`]) implement `AsyncIterator`.
+- `ops::from_std` wraps a stdlib coroutine, so it can be used as a [`Coroutine`].
+- `GeneratorState` converts to and from the stdlib's `CoroutineState` with `From`.
 */
 
-#![cfg_attr(feature = "nightly", feature(async_closure))]
+#![cfg_attr(feature = "nightly", feature(async_iterator, coroutine_trait))]
+#![cfg_attr(all(test, feature = "nightly"), feature(coroutines, yield_expr))]
 #![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
 #![warn(missing_docs, clippy::cargo, clippy::pedantic)]
 #![cfg_attr(feature = "strict", deny(warnings))]
@@ -264,7 +349,10 @@ resume argument type to `()`, but in a `Coroutine` it can be anything.
 #[cfg(test)]
 extern crate self as genawaiter;
 
-pub use crate::ops::{Coroutine, Generator, GeneratorState};
+pub use crate::{
+    core::{Airlock, Co, ProducerFn},
+    ops::{Coroutine, Generator, GeneratorState},
+};
 
 #[cfg(feature = "proc_macro")]
 use proc_macro_hack::proc_macro_hack;
@@ -290,7 +378,7 @@ use proc_macro_hack::proc_macro_hack;
 /// # my_generator.resume();
 /// ```
 #[cfg(feature = "proc_macro")]
-#[proc_macro_hack]
+#[proc_macro_hack(support_nested)]
 pub use genawaiter_proc_macro::sync_producer;
 
 /// Creates a producer for use with [`rc::Gen`].
@@ -314,22 +402,213 @@ pub use genawaiter_proc_macro::sync_producer;
 /// # my_generator.resume();
 /// ```
 #[cfg(feature = "proc_macro")]
-#[proc_macro_hack]
+#[proc_macro_hack(support_nested)]
 pub use genawaiter_proc_macro::rc_producer;
 
+/// Turns a function into a producer which works with every engine.
+///
+/// This is like the `producer_fn` attribute of each engine, such as
+/// [`sync::producer_fn`], except that the producer accepts a
+/// `Co<impl Airlock>`, so it can be passed to the `Gen::new` of `rc`, `sync`,
+/// or `stack`. See [`Airlock`].
+///
+/// The body of the function should contain one or more [`yield_!`]
+/// expressions. Like the per-engine attributes, it accepts a resume type, as
+/// in `#[producer_fn(u8, resume = &str)]`, and functions with arguments.
+///
+/// # Example
+///
+/// ```rust
+/// use genawaiter::{producer_fn, rc, sync, yield_};
+///
+/// #[producer_fn(u8)]
+/// async fn odds() {
+///     for n in (1_u8..).step_by(2).take_while(|&n| n < 10) {
+///         yield_!(n);
+///     }
+/// }
+///
+/// let rc_items: Vec<_> = rc::Gen::new(odds).into_iter().collect();
+/// let sync_items: Vec<_> = sync::Gen::new(odds).into_iter().collect();
+/// assert_eq!(rc_items, [1, 3, 5, 7, 9]);
+/// assert_eq!(sync_items, [1, 3, 5, 7, 9]);
+/// ```
+#[cfg(feature = "proc_macro")]
+pub use genawaiter_proc_macro::producer_fn;
+
+/// Turns a function into one which returns an iterator, or a stream if it is
+/// `async`.
+///
+/// The body of the function should contain one or more [`yield_!`]
+/// expressions. The function's return type must be `impl Iterator<Item = T>`
+/// (or `impl Stream<Item = T>`), and `T` is the yield type. It can also be
+/// given explicitly, as in `#[generator(yield = T)]`.
+///
+/// The generator is built with the [`rc`] engine, or with [`sync`] if the
+/// return type includes `+ Send`. Callers only see the opaque return type.
+///
+/// It also works on methods, including trait methods. A trait method cannot
+/// return `impl Trait`, so it can return `Box<dyn Iterator<Item = T>>` (or
+/// `Pin<Box<dyn Stream<Item = T>>>`) instead, and the generator is boxed. If
+/// the method borrows `self`, the returned type borrows it too.
+///
+/// # Examples
+///
+/// ```rust
+/// use genawaiter::{generator, yield_};
+///
+/// #[generator(yield = u32)]
+/// fn odds(limit: u32) -> impl Iterator<Item = u32> {
+///     for n in (1..limit).step_by(2) {
+///         yield_!(n);
+///     }
+/// }
+///
+/// assert_eq!(odds(10).collect::<Vec<_>>(), [1, 3, 5, 7, 9]);
+/// ```
+///
+/// On a trait method:
+///
+/// ```rust
+/// use genawaiter::{generator, yield_};
+///
+/// trait Shape {
+///     fn corners(&self) -> Box<dyn Iterator<Item = (i32, i32)> + '_>;
+/// }
+///
+/// struct Square(i32);
+///
+/// impl Shape for Square {
+///     #[generator]
+///     fn corners(&self) -> Box<dyn Iterator<Item = (i32, i32)> + '_> {
+///         for &(x, y) in &[(0, 0), (1, 0), (1, 1), (0, 1)] {
+///             yield_!((x * self.0, y * self.0));
+///         }
+///     }
+/// }
+///
+/// let corners: Vec<_> = Square(2).corners().collect();
+/// assert_eq!(corners, [(0, 0), (2, 0), (2, 2), (0, 2)]);
+/// ```
+///
+/// With the `futures03` feature, an `async fn` becomes a stream:
+///
+/// ```rust
+/// # #[cfg(feature = "futures03")]
+/// # fn feature_gate() {
+/// use futures::{executor::block_on_stream, Stream};
+/// use genawaiter::{generator, yield_};
+///
+/// #[generator]
+/// async fn countdown(from: u32) -> impl Stream<Item = u32> + Send {
+///     for n in (1..=from).rev() {
+///         yield_!(n);
+///     }
+/// }
+///
+/// let items: Vec<_> = block_on_stream(Box::pin(countdown(3))).collect();
+/// assert_eq!(items, [3, 2, 1]);
+/// # }
+/// ```
+#[cfg(feature = "proc_macro")]
+pub use genawaiter_proc_macro::generator;
+
+/// Implements `IntoIterator` for references to a type, using one of its
+/// generator methods.
+///
+/// Place `#[into_iter]` on an inherent `impl` block, and on one method in it.
+/// The method takes `&self` (or `&mut self`), and its body contains one or more
+/// [`yield_!`] expressions, like a [`generator`] method. The yield type is
+/// given in the attribute, as in `#[into_iter(yield = T)]`, or in the return
+/// type, as `impl Iterator<Item = T>`. Without a return type, the method
+/// returns `Box<dyn Iterator<Item = T> + '_>`.
+///
+/// Then `&MyType` (or `&mut MyType`) implements `IntoIterator`, so it can be
+/// used in a `for` loop. Its `IntoIter` type is a boxed iterator, so it can be
+/// named.
+///
+/// # Examples
+///
+/// ```rust
+/// use genawaiter::{into_iter, yield_};
+///
+/// struct Grid {
+///     rows: Vec<Vec<u8>>,
+/// }
+///
+/// #[into_iter]
+/// impl Grid {
+///     #[into_iter(yield = &u8)]
+///     fn cells(&self) {
+///         for row in &self.rows {
+///             for cell in row {
+///                 yield_!(cell);
+///             }
+///         }
+///     }
+/// }
+///
+/// let grid = Grid {
+///     rows: vec![vec![1, 2], vec![3]],
+/// };
+/// let mut sum = 0;
+/// for cell in &grid {
+///     sum += cell;
+/// }
+/// assert_eq!(sum, 6);
+/// assert_eq!(grid.cells().count(), 3);
+/// ```
+#[cfg(feature = "proc_macro")]
+pub use genawaiter_proc_macro::into_iter;
+
 #[doc(hidden)] // This is not quite usable currently, so hide it for now.
 #[cfg(feature = "proc_macro")]
-#[proc_macro_hack]
+#[proc_macro_hack(support_nested)]
 pub use genawaiter_proc_macro::stack_producer;
 
+#[doc(hidden)] // Used by `stack::let_send_gen!`.
+#[cfg(feature = "proc_macro")]
+#[proc_macro_hack(support_nested)]
+pub use genawaiter_proc_macro::stack_send_producer;
+
+#[doc(hidden)] // Used by `rc::iter_gen!` and `sync::iter_gen!`.
+#[cfg(feature = "proc_macro")]
+#[proc_macro_hack(support_nested)]
+pub use genawaiter_proc_macro::iter_producer;
+
+#[doc(hidden)] // Used by `rc::stream!` and `sync::stream!`.
+#[cfg(all(feature = "proc_macro", feature = "futures03"))]
+#[proc_macro_hack(support_nested)]
+pub use genawaiter_proc_macro::stream_producer;
+
+#[doc(hidden)] // Used by `rc::try_stream!` and `sync::try_stream!`.
+#[cfg(all(feature = "proc_macro", feature = "futures03"))]
+#[proc_macro_hack(support_nested)]
+pub use genawaiter_proc_macro::try_stream_producer;
+
+#[doc(hidden)] // Used by macro expansions. Not part of the public API.
+pub mod __private {
+    #[cfg(feature = "futures03")]
+    pub use futures_core::Stream;
+
+    /// Hides the type of a stream, so a `stream!` evaluates to an opaque
+    /// `impl Stream`.
+    #[cfg(feature = "futures03")]
+    pub fn into_stream<S: Stream>(stream: S) -> impl Stream<Item = S::Item> {
+        stream
+    }
+}
+
 mod core;
-mod ext;
 #[macro_use]
 mod macros;
-mod ops;
+pub mod engine;
+pub mod inline;
+pub mod ops;
 pub mod rc;
 pub mod stack;
 pub mod sync;
 #[cfg(test)]
 mod testing;
+pub mod thread;
 mod waker;
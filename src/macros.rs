@@ -3,6 +3,10 @@
 /// This macro can only be used inside the `gen!` and `producer!` families of
 /// macros.
 ///
+/// It yields from the innermost generator around it, so generators can be
+/// nested. It cannot be used inside a closure, an `async` block, or a nested
+/// function within the generator, since none of them can suspend the generator.
+///
 /// It will suspend execution of the function until the generator is resumed. At
 /// that time, it will evaluate to the resume argument, if given, otherwise it
 /// will evaluate to `()`.
@@ -15,7 +19,9 @@
 macro_rules! yield_ {
     ($val:expr) => {
         compile_error!(
-            "`yield_!()` can only be used inside one of the genawaiter macros",
+            "`yield_!()` can only be used directly inside a generator: in the body of \
+             `gen!`, `producer!`, `#[producer_fn]`, or `#[generator]`, and not in a \
+             nested function",
         )
     };
     (@__impl => $co:expr, $value:expr) => {
@@ -25,7 +31,6 @@ macro_rules! yield_ {
 
 // Internal use only. This is a copy of `futures::pin_mut!` so we can avoid
 // pulling in a dependency for a two-liner.
-#[cfg(feature = "futures03")]
 macro_rules! pin_mut {
     ($x:ident) => {
         let mut $x = $x;
@@ -0,0 +1,141 @@
+use crate::ops::{Coroutine, GeneratorState};
+use std::{marker::PhantomData, pin::Pin};
+
+/// Creates a coroutine from a hand-written state machine.
+///
+/// Each time the result is resumed, `f` is called with the resume argument, and
+/// whatever it returns is passed through. This lets a plain closure stand in
+/// wherever a coroutine is expected, without allocating a generator.
+///
+/// The closure decides for itself what to do if it is called again after it has
+/// returned `Complete`.
+///
+/// # Example
+///
+/// ```rust
+/// use genawaiter::{ops::from_fn, GeneratorState};
+///
+/// let mut total = 0;
+/// let mut adder = from_fn(|n: i32| {
+///     total += n;
+///     if total < 10 {
+///         GeneratorState::Yielded(total)
+///     } else {
+///         GeneratorState::Complete("full")
+///     }
+/// });
+/// assert_eq!(adder.resume_with(4), GeneratorState::Yielded(4));
+/// assert_eq!(adder.resume_with(5), GeneratorState::Yielded(9));
+/// assert_eq!(adder.resume_with(6), GeneratorState::Complete("full"));
+/// ```
+pub fn from_fn<Y, R, C, F>(f: F) -> FromFn<F, R>
+where
+    F: FnMut(R) -> GeneratorState<Y, C>,
+{
+    FromFn {
+        f,
+        resume: PhantomData,
+    }
+}
+
+/// A coroutine which calls a closure each time it is resumed.
+///
+/// This is created by [`from_fn`]. See its documentation for more.
+#[allow(clippy::module_name_repetitions)]
+pub struct FromFn<F, R> {
+    f: F,
+    resume: PhantomData<fn(R)>,
+}
+
+// The closure is never pinned.
+impl<F, R> Unpin for FromFn<F, R> {}
+
+impl<Y, R, C, F> FromFn<F, R>
+where
+    F: FnMut(R) -> GeneratorState<Y, C>,
+{
+    /// Calls the closure with the resume argument `arg`.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(&mut self, arg: R) -> GeneratorState<Y, C> {
+        (self.f)(arg)
+    }
+}
+
+impl<Y, C, F> FromFn<F, ()>
+where
+    F: FnMut(()) -> GeneratorState<Y, C>,
+{
+    /// Calls the closure.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume(&mut self) -> GeneratorState<Y, C> {
+        self.resume_with(())
+    }
+}
+
+impl<Y, R, C, F> Coroutine for FromFn<F, R>
+where
+    F: FnMut(R) -> GeneratorState<Y, C>,
+{
+    type Yield = Y;
+    type Resume = R;
+    type Return = C;
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: R,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume_with(&mut *self, arg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::{from_fn, zip},
+        rc::{Co, Gen},
+        GeneratorState,
+    };
+
+    #[test]
+    fn generator() {
+        let mut n = 0;
+        let mut gen = from_fn(|()| {
+            n += 1;
+            if n <= 2 {
+                GeneratorState::Yielded(n)
+            } else {
+                GeneratorState::Complete(())
+            }
+        });
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+        assert_eq!(gen.resume(), GeneratorState::Yielded(2));
+        assert_eq!(gen.resume(), GeneratorState::Complete(()));
+    }
+
+    #[test]
+    fn mixed_with_gen() {
+        async fn produce(mut co: Co<i32>) -> &'static str {
+            co.yield_(10).await;
+            "gen"
+        }
+
+        let mut n = 0;
+        let counter = from_fn(move |()| {
+            n += 1;
+            if n <= 2 {
+                GeneratorState::Yielded(n)
+            } else {
+                GeneratorState::Complete("fn")
+            }
+        });
+        let mut zipped = zip(counter, Gen::new(produce));
+        assert_eq!(
+            zipped.resume(),
+            GeneratorState::Yielded((Some(1), Some(10)))
+        );
+        assert_eq!(zipped.resume(), GeneratorState::Yielded((Some(2), None)));
+        assert_eq!(zipped.resume(), GeneratorState::Complete(("fn", "gen")));
+    }
+}
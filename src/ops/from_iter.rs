@@ -0,0 +1,101 @@
+use crate::ops::{Coroutine, GeneratorState};
+use std::pin::Pin;
+
+/// Creates a coroutine which yields the items of an iterator.
+///
+/// The result yields each item in turn, and completes with `()` once the
+/// iterator is exhausted. It takes `()` as its resume argument, so it is also a
+/// [`Generator`](crate::Generator), and it can be passed to any function which
+/// accepts a coroutine, e.g., the combinators in this module.
+///
+/// Nothing is allocated; the iterator is stored inline.
+///
+/// # Example
+///
+/// ```rust
+/// use genawaiter::{
+///     ops::{from_iter, zip},
+///     rc::{Co, Gen},
+///     GeneratorState,
+/// };
+///
+/// async fn letters(mut co: Co<char>) {
+///     co.yield_('a').await;
+///     co.yield_('b').await;
+/// }
+///
+/// let mut zipped = zip(from_iter(1..=2), Gen::new(letters));
+/// assert_eq!(
+///     zipped.resume(),
+///     GeneratorState::Yielded((Some(1), Some('a')))
+/// );
+/// assert_eq!(
+///     zipped.resume(),
+///     GeneratorState::Yielded((Some(2), Some('b')))
+/// );
+/// assert_eq!(zipped.resume(), GeneratorState::Complete(((), ())));
+/// ```
+pub fn from_iter<I: IntoIterator>(iter: I) -> FromIter<I::IntoIter> {
+    FromIter(iter.into_iter())
+}
+
+/// A coroutine which yields the items of an iterator.
+///
+/// This is created by [`from_iter`]. See its documentation for more.
+#[allow(clippy::module_name_repetitions)]
+pub struct FromIter<I>(I);
+
+// The iterator is never pinned.
+impl<I> Unpin for FromIter<I> {}
+
+impl<I: Iterator> FromIter<I> {
+    /// Takes the next item from the iterator.
+    ///
+    /// If the iterator has an item, `Yielded` is returned. Otherwise,
+    /// `Completed` is returned.
+    pub fn resume(&mut self) -> GeneratorState<I::Item, ()> {
+        match self.0.next() {
+            Some(item) => GeneratorState::Yielded(item),
+            None => GeneratorState::Complete(()),
+        }
+    }
+}
+
+impl<I: Iterator> Coroutine for FromIter<I> {
+    type Yield = I::Item;
+    type Resume = ();
+    type Return = ();
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        (): (),
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume(&mut *self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ops::from_iter, Generator, GeneratorState};
+    use std::pin::Pin;
+
+    #[test]
+    fn yields_items() {
+        let mut gen = from_iter(vec![1, 2]);
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+        assert_eq!(gen.resume(), GeneratorState::Yielded(2));
+        assert_eq!(gen.resume(), GeneratorState::Complete(()));
+    }
+
+    #[test]
+    fn is_a_generator() {
+        fn first<G: Generator + Unpin>(
+            mut gen: G,
+        ) -> GeneratorState<G::Yield, G::Return> {
+            Pin::new(&mut gen).resume()
+        }
+
+        assert_eq!(first(from_iter("ab".chars())), GeneratorState::Yielded('a'));
+        assert_eq!(first(from_iter(None::<i32>)), GeneratorState::Complete(()));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::ops::{AsyncCoroutine, GeneratorState};
+use futures_core::Stream;
+use std::{
+    future::{self, Future},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Creates an async coroutine which yields the items of a stream.
+///
+/// The result yields each item in turn, and completes with `()` once the stream
+/// is exhausted. It implements [`AsyncCoroutine`], so it can be passed anywhere
+/// the async generators of this crate are accepted, e.g., to [`select`].
+///
+/// Nothing is allocated; the stream is stored inline.
+///
+/// [`select`]: crate::ops::select
+///
+/// # Example
+///
+/// ```rust
+/// # async fn feature_gate() {
+/// use futures::stream;
+/// use genawaiter::{ops::from_stream, GeneratorState};
+///
+/// let mut gen = from_stream(stream::iter(vec![1, 2]));
+/// assert_eq!(gen.async_resume().await, GeneratorState::Yielded(1));
+/// assert_eq!(gen.async_resume().await, GeneratorState::Yielded(2));
+/// assert_eq!(gen.async_resume().await, GeneratorState::Complete(()));
+/// # }
+/// ```
+pub fn from_stream<S: Stream>(stream: S) -> FromStream<S> {
+    FromStream(stream)
+}
+
+/// An async coroutine which yields the items of a stream.
+///
+/// This is created by [`from_stream`]. See its documentation for more.
+#[allow(clippy::module_name_repetitions)]
+pub struct FromStream<S>(S);
+
+impl<S: Stream + Unpin> FromStream<S> {
+    /// Takes the next item from the stream.
+    ///
+    /// If the stream is not ready, `Poll::Pending` is returned. If the stream
+    /// has an item, `Poll::Ready(Yielded)` is returned. Otherwise,
+    /// `Poll::Ready(Completed)` is returned.
+    pub fn async_resume(
+        &mut self,
+    ) -> impl Future<Output = GeneratorState<S::Item, ()>> + '_ {
+        future::poll_fn(move |cx| Pin::new(&mut *self).poll_resume(cx, ()))
+    }
+}
+
+impl<S: Stream> AsyncCoroutine for FromStream<S> {
+    type Yield = S::Item;
+    type Resume = ();
+    type Return = ();
+
+    fn poll_resume(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        (): (),
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        // Safety: The stream is pinned structurally. It is never moved out of
+        // `self`, and `FromStream` is only `Unpin` if the stream is.
+        let stream = unsafe { self.map_unchecked_mut(|s| &mut s.0) };
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => Poll::Ready(GeneratorState::Yielded(item)),
+            Poll::Ready(None) => Poll::Ready(GeneratorState::Complete(())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::{from_stream, select},
+        testing::SlowFuture,
+        GeneratorState,
+    };
+    use futures::{executor::block_on, stream, StreamExt};
+
+    #[test]
+    fn yields_items() {
+        block_on(async {
+            let slow = stream::iter(vec![1, 2]).then(|x| {
+                async move {
+                    SlowFuture::new().await;
+                    x
+                }
+            });
+            let mut gen = from_stream(Box::pin(slow));
+            assert_eq!(gen.async_resume().await, GeneratorState::Yielded(1));
+            assert_eq!(gen.async_resume().await, GeneratorState::Yielded(2));
+            assert_eq!(gen.async_resume().await, GeneratorState::Complete(()));
+        });
+    }
+
+    #[test]
+    fn selectable() {
+        block_on(async {
+            let streams = vec![stream::iter(vec![1, 3]), stream::iter(vec![2])];
+            let mut gen = select(streams.into_iter().map(from_stream));
+            let mut items = Vec::new();
+            let returns = loop {
+                match gen.async_resume().await {
+                    GeneratorState::Yielded(x) => items.push(x),
+                    GeneratorState::Complete(returns) => break returns,
+                }
+            };
+            assert_eq!(items, [1, 2, 3]);
+            assert_eq!(returns, [(), ()]);
+        });
+    }
+}
@@ -0,0 +1,157 @@
+use crate::ops::{Coroutine, GeneratorState, Slot};
+use std::pin::Pin;
+
+/// Combines several coroutines into one which takes turns resuming each of them.
+///
+/// Each time the result is resumed, the next input in round-robin order is resumed
+/// with a clone of the resume argument, and its value is yielded. Inputs which have
+/// completed are skipped. The result completes once every input has completed,
+/// with their completion values in the same order as the inputs.
+///
+/// # Example
+///
+/// ```rust
+/// use genawaiter::{
+///     ops::interleave,
+///     rc::{Co, Gen},
+/// };
+///
+/// async fn spell(word: &'static str, mut co: Co<char>) {
+///     for c in word.chars() {
+///         co.yield_(c).await;
+///     }
+/// }
+///
+/// let words = vec!["abc", "1"]
+///     .into_iter()
+///     .map(|w| Gen::new(move |co| spell(w, co)));
+/// let result: String = interleave(words).collect();
+/// assert_eq!(result, "a1bc");
+/// ```
+pub fn interleave<C, I>(coroutines: I) -> Interleave<C>
+where
+    I: IntoIterator<Item = C>,
+    C: Coroutine + Unpin,
+    C::Resume: Clone,
+{
+    Interleave {
+        slots: coroutines.into_iter().map(Slot::Running).collect(),
+        next: 0,
+    }
+}
+
+/// A coroutine which resumes several coroutines in round-robin order.
+///
+/// This is created by [`interleave`]. See its documentation for more.
+pub struct Interleave<C: Coroutine> {
+    slots: Vec<Slot<C, C::Return>>,
+    next: usize,
+}
+
+// The inputs are only ever pinned through `Pin::new`, which requires `Unpin`, and
+// the completion values are never pinned at all.
+impl<C: Coroutine> Unpin for Interleave<C> {}
+
+impl<C> Interleave<C>
+where
+    C: Coroutine + Unpin,
+    C::Resume: Clone,
+{
+    /// Resumes the next input which has not yet completed.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(
+        &mut self,
+        arg: C::Resume,
+    ) -> GeneratorState<C::Yield, Vec<C::Return>> {
+        let len = self.slots.len();
+        for _ in 0..len {
+            let index = self.next;
+            self.next = (index + 1) % len;
+            if let Some(value) = self.slots[index].resume_with(arg.clone()) {
+                return GeneratorState::Yielded(value);
+            }
+        }
+        GeneratorState::Complete(self.slots.iter_mut().map(Slot::take_return).collect())
+    }
+}
+
+impl<C: Coroutine<Resume = ()> + Unpin> Interleave<C> {
+    /// Resumes the next input which has not yet completed.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume(&mut self) -> GeneratorState<C::Yield, Vec<C::Return>> {
+        self.resume_with(())
+    }
+}
+
+impl<C> Coroutine for Interleave<C>
+where
+    C: Coroutine + Unpin,
+    C::Resume: Clone,
+{
+    type Yield = C::Yield;
+    type Resume = C::Resume;
+    type Return = Vec<C::Return>;
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: C::Resume,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume_with(&mut *self, arg)
+    }
+}
+
+impl<C: Coroutine<Resume = ()> + Unpin> Iterator for Interleave<C> {
+    type Item = C::Yield;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.resume() {
+            GeneratorState::Yielded(x) => Some(x),
+            GeneratorState::Complete(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::interleave,
+        rc::{Co, Gen},
+        GeneratorState,
+    };
+
+    async fn count_to(n: i32, mut co: Co<i32>) -> i32 {
+        for i in 1..=n {
+            co.yield_(i).await;
+        }
+        -n
+    }
+
+    #[test]
+    fn round_robin() {
+        let gens = vec![
+            Gen::new(|co| count_to(1, co)),
+            Gen::new(|co| count_to(3, co)),
+            Gen::new(|co| count_to(2, co)),
+        ];
+        let mut gen = interleave(gens);
+        let mut items = Vec::new();
+        let returns = loop {
+            match gen.resume() {
+                GeneratorState::Yielded(x) => items.push(x),
+                GeneratorState::Complete(returns) => break returns,
+            }
+        };
+        assert_eq!(items, [1, 1, 1, 2, 2, 3]);
+        assert_eq!(returns, [-1, -3, -2]);
+    }
+
+    #[test]
+    fn empty() {
+        let mut gens = vec![Gen::new(|co| count_to(1, co))];
+        gens.clear();
+        let mut gen = interleave(gens);
+        assert_eq!(gen.resume(), GeneratorState::Complete(vec![]));
+    }
+}
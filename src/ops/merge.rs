@@ -0,0 +1,220 @@
+use crate::ops::{Coroutine, GeneratorState, Slot};
+use std::pin::Pin;
+
+/// Merges several coroutines, which each yield values in ascending order of some
+/// key, into one which yields all their values in ascending order of that key.
+///
+/// The first time the result is resumed, every input is resumed once with a clone
+/// of the resume argument, so that the smallest value can be found. After that,
+/// only the input whose value was just yielded is resumed. When several values have
+/// equal keys, the one from the earliest input is yielded first. The result
+/// completes once every input has completed, with their completion values in the
+/// same order as the inputs.
+///
+/// If an input does not yield in ascending order, neither will the result.
+///
+/// # Example
+///
+/// ```rust
+/// use genawaiter::{
+///     ops::merge_by,
+///     rc::{Co, Gen},
+/// };
+///
+/// async fn shard(rows: Vec<(u32, &'static str)>, mut co: Co<(u32, &'static str)>) {
+///     for row in rows {
+///         co.yield_(row).await;
+///     }
+/// }
+///
+/// let shard_a = vec![(1, "apple"), (4, "date")];
+/// let shard_b = vec![(2, "banana"), (3, "cherry")];
+/// let gens = vec![shard_a, shard_b]
+///     .into_iter()
+///     .map(|rows| Gen::new(move |co| shard(rows, co)));
+///
+/// let merged = merge_by(gens, |&(id, _)| id);
+/// let names: Vec<_> = merged.map(|(_, name)| name).collect();
+/// assert_eq!(names, ["apple", "banana", "cherry", "date"]);
+/// ```
+pub fn merge_by<C, I, K, F>(coroutines: I, key: F) -> MergeBy<C, F>
+where
+    I: IntoIterator<Item = C>,
+    C: Coroutine + Unpin,
+    C::Resume: Clone,
+    K: Ord,
+    F: FnMut(&C::Yield) -> K,
+{
+    let slots: Vec<_> = coroutines.into_iter().map(Slot::Running).collect();
+    let heads = slots.iter().map(|_| None).collect();
+    MergeBy {
+        slots,
+        heads,
+        key,
+        started: false,
+        last: None,
+    }
+}
+
+/// A coroutine which merges several sorted coroutines.
+///
+/// This is created by [`merge_by`]. See its documentation for more.
+pub struct MergeBy<C: Coroutine, F> {
+    slots: Vec<Slot<C, C::Return>>,
+    /// The next value from each input, waiting to be yielded.
+    heads: Vec<Option<C::Yield>>,
+    key: F,
+    started: bool,
+    /// The input whose value was yielded most recently.
+    last: Option<usize>,
+}
+
+// The inputs are only ever pinned through `Pin::new`, which requires `Unpin`, and
+// nothing else is ever pinned at all.
+impl<C: Coroutine, F> Unpin for MergeBy<C, F> {}
+
+impl<C, K, F> MergeBy<C, F>
+where
+    C: Coroutine + Unpin,
+    C::Resume: Clone,
+    K: Ord,
+    F: FnMut(&C::Yield) -> K,
+{
+    /// Resumes the inputs as needed, and yields the value with the smallest key.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(
+        &mut self,
+        arg: C::Resume,
+    ) -> GeneratorState<C::Yield, Vec<C::Return>> {
+        if !self.started {
+            self.started = true;
+            for (slot, head) in self.slots.iter_mut().zip(&mut self.heads) {
+                *head = slot.resume_with(arg.clone());
+            }
+        } else if let Some(index) = self.last {
+            self.heads[index] = self.slots[index].resume_with(arg);
+        }
+
+        let mut smallest: Option<(usize, K)> = None;
+        for (index, head) in self.heads.iter().enumerate() {
+            if let Some(value) = head {
+                let key = (self.key)(value);
+                match &smallest {
+                    Some((_, smallest_key)) if *smallest_key <= key => {}
+                    _ => smallest = Some((index, key)),
+                }
+            }
+        }
+
+        self.last = smallest.map(|(index, _)| index);
+        match self.last.and_then(|index| self.heads[index].take()) {
+            Some(value) => GeneratorState::Yielded(value),
+            None => {
+                GeneratorState::Complete(
+                    self.slots.iter_mut().map(Slot::take_return).collect(),
+                )
+            }
+        }
+    }
+}
+
+impl<C, K, F> MergeBy<C, F>
+where
+    C: Coroutine<Resume = ()> + Unpin,
+    K: Ord,
+    F: FnMut(&C::Yield) -> K,
+{
+    /// Resumes the inputs as needed, and yields the value with the smallest key.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume(&mut self) -> GeneratorState<C::Yield, Vec<C::Return>> {
+        self.resume_with(())
+    }
+}
+
+impl<C, K, F> Coroutine for MergeBy<C, F>
+where
+    C: Coroutine + Unpin,
+    C::Resume: Clone,
+    K: Ord,
+    F: FnMut(&C::Yield) -> K,
+{
+    type Yield = C::Yield;
+    type Resume = C::Resume;
+    type Return = Vec<C::Return>;
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: C::Resume,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume_with(&mut *self, arg)
+    }
+}
+
+impl<C, K, F> Iterator for MergeBy<C, F>
+where
+    C: Coroutine<Resume = ()> + Unpin,
+    K: Ord,
+    F: FnMut(&C::Yield) -> K,
+{
+    type Item = C::Yield;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.resume() {
+            GeneratorState::Yielded(x) => Some(x),
+            GeneratorState::Complete(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::merge_by,
+        rc::{Co, Gen},
+        GeneratorState,
+    };
+
+    async fn produce(items: Vec<(i32, char)>, mut co: Co<(i32, char)>) -> usize {
+        let len = items.len();
+        for item in items {
+            co.yield_(item).await;
+        }
+        len
+    }
+
+    #[test]
+    fn k_way_merge() {
+        let shards = vec![
+            vec![(1, 'a'), (5, 'e'), (9, 'i')],
+            vec![],
+            vec![(2, 'b'), (3, 'c'), (4, 'd')],
+            vec![(6, 'f'), (7, 'g'), (8, 'h')],
+        ];
+        let gens = shards
+            .into_iter()
+            .map(|shard| Gen::new(move |co| produce(shard, co)));
+        let mut gen = merge_by(gens, |&(n, _)| n);
+
+        let mut items = String::new();
+        let returns = loop {
+            match gen.resume() {
+                GeneratorState::Yielded((_, c)) => items.push(c),
+                GeneratorState::Complete(returns) => break returns,
+            }
+        };
+        assert_eq!(items, "abcdefghi");
+        assert_eq!(returns, [3, 0, 3, 3]);
+    }
+
+    #[test]
+    fn ties_prefer_earlier_inputs() {
+        let shards = vec![vec![(1, 'a'), (2, 'c')], vec![(1, 'b'), (2, 'd')]];
+        let gens = shards
+            .into_iter()
+            .map(|shard| Gen::new(move |co| produce(shard, co)));
+        let items: String = merge_by(gens, |&(n, _)| n).map(|(_, c)| c).collect();
+        assert_eq!(items, "abcd");
+    }
+}
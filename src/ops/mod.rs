@@ -0,0 +1,530 @@
+/*!
+Traits shared by all generators, and functions for combining several generators into
+one.
+
+The combinators in this module work with any [`Coroutine`], so generators from
+different engines (and different producers) can be mixed freely, as long as their
+types line up.
+
+Async generators implement [`AsyncCoroutine`], the async counterpart of `Coroutine`.
+Code which is generic over either trait works with the generators of every engine.
+Both traits are also implemented for `&mut C`, `Box<C>` and `Pin<Box<C>>`, and every
+engine's `Gen` has a `boxed` method which erases its type into a [`BoxCoroutine`].
+
+- [`zip`] and [`zip_split`] – Resume two coroutines in lockstep and yield pairs.
+- [`interleave`] – Take turns resuming each coroutine, round-robin.
+- [`merge_by`] – Merge coroutines which each yield in sorted order into one sorted
+  sequence.
+- [`select`] – Yield from whichever async generator is ready first.
+
+Coroutines don't have to come from a generator. These adapters wrap other types,
+without allocating, so they can be mixed with generators:
+
+- [`from_iter`] – Yield the items of an `Iterator`.
+- [`from_fn`] – Call a closure which returns a `GeneratorState` on each resumption.
+- `from_stream` – Yield the items of a `Stream`. This requires the `futures03`
+  feature.
+- `from_std` – Wrap one of the stdlib's nightly-only coroutines. This requires the
+  `nightly` feature.
+
+Each combinator completes only once every input has completed, and returns all of
+their completion values.
+
+```rust
+use genawaiter::{
+    ops::merge_by,
+    rc::{Co, Gen},
+};
+
+async fn multiples_of(n: i32, mut co: Co<i32>) {
+    for i in 1..=3 {
+        co.yield_(n * i).await;
+    }
+}
+
+let shards = vec![2, 3].into_iter().map(|n| Gen::new(move |co| multiples_of(n, co)));
+let merged: Vec<_> = merge_by(shards, |&x| x).collect();
+assert_eq!(merged, [2, 3, 4, 6, 6, 9]);
+```
+*/
+
+#[cfg(feature = "futures03")]
+pub use crate::ops::from_stream::{from_stream, FromStream};
+#[cfg(feature = "nightly")]
+pub use crate::ops::nightly::{from_std, FromStd};
+pub use crate::ops::{
+    from_fn::{from_fn, FromFn},
+    from_iter::{from_iter, FromIter},
+    interleave::{interleave, Interleave},
+    merge::{merge_by, MergeBy},
+    select::{select, Select},
+    zip::{zip, zip_split, Zip, ZipSplit},
+};
+use std::{
+    mem,
+    ops::DerefMut,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+mod from_fn;
+mod from_iter;
+#[cfg(feature = "futures03")]
+mod from_stream;
+mod interleave;
+mod merge;
+#[cfg(feature = "nightly")]
+mod nightly;
+mod select;
+mod zip;
+
+#[cfg(feature = "nightly")]
+#[cfg(test)]
+mod nightly_tests;
+
+/// A trait implemented for coroutines.
+///
+/// A `Coroutine` is a generalization of a `Generator`. A `Generator` constrains
+/// the resume argument type to `()`, but in a `Coroutine` it can be anything.
+pub trait Coroutine {
+    /// The type of value this generator yields.
+    type Yield;
+
+    /// The type of value this generator accepts as a resume argument.
+    type Resume;
+
+    /// The type of value this generator returns upon completion.
+    type Return;
+
+    /// Resumes the execution of this generator.
+    ///
+    /// The argument will be passed into the coroutine as a resume argument.
+    fn resume_with(
+        self: Pin<&mut Self>,
+        arg: Self::Resume,
+    ) -> GeneratorState<Self::Yield, Self::Return>;
+}
+
+/// A trait implemented for async coroutines.
+///
+/// This is the async counterpart of [`Coroutine`]. It is implemented for the
+/// generators of every engine, so generic code can accept any of them. Resuming
+/// may need to wait (if the producer awaits something other than `co.yield_`),
+/// so it is driven by polling, the same way as a `Future`.
+///
+/// ```rust
+/// # async fn feature_gate() {
+/// use genawaiter::{ops::AsyncCoroutine, rc, sync, GeneratorState};
+/// use std::{future, pin::Pin};
+///
+/// async fn sum<G>(mut gen: G) -> i32
+/// where
+///     G: AsyncCoroutine<Yield = i32, Resume = ()> + Unpin,
+/// {
+///     let mut total = 0;
+///     loop {
+///         let next = future::poll_fn(|cx| Pin::new(&mut gen).poll_resume(cx, ()));
+///         match next.await {
+///             GeneratorState::Yielded(n) => total += n,
+///             GeneratorState::Complete(_) => return total,
+///         }
+///     }
+/// }
+///
+/// assert_eq!(
+///     sum(rc::Gen::new(|mut co| async move { co.yield_(1).await })).await,
+///     1
+/// );
+/// assert_eq!(
+///     sum(sync::Gen::new(|mut co| async move { co.yield_(2).await })).await,
+///     2
+/// );
+/// # }
+/// ```
+pub trait AsyncCoroutine {
+    /// The type of value this generator yields.
+    type Yield;
+
+    /// The type of value this generator accepts as a resume argument.
+    type Resume;
+
+    /// The type of value this generator returns upon completion.
+    type Return;
+
+    /// Attempts to resume the execution of this generator.
+    ///
+    /// If the generator pauses without yielding, `Poll::Pending` is returned,
+    /// and the waker in `cx` is woken once it can make progress. If the
+    /// generator yields a value, `Poll::Ready(Yielded)` is returned. Otherwise,
+    /// `Poll::Ready(Completed)` is returned.
+    ///
+    /// `arg` is passed into the generator as the resume argument when it is
+    /// paused at a yield. If the previous call returned `Poll::Pending`, the
+    /// generator is still partway through that resumption, so `arg` is dropped.
+    fn poll_resume(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        arg: Self::Resume,
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>>;
+}
+
+/// A trait implemented for generator types.
+///
+/// This is modeled after the stdlib's nightly-only [`std::ops::Generator`].
+pub trait Generator {
+    /// The type of value this generator yields.
+    type Yield;
+
+    /// The type of value this generator returns upon completion.
+    type Return;
+
+    /// Resumes the execution of this generator.
+    fn resume(self: Pin<&mut Self>) -> GeneratorState<Self::Yield, Self::Return>;
+}
+
+impl<C: Coroutine<Resume = ()> + ?Sized> Generator for C {
+    type Yield = <Self as Coroutine>::Yield;
+    type Return = <Self as Coroutine>::Return;
+
+    fn resume(self: Pin<&mut Self>) -> GeneratorState<Self::Yield, Self::Return> {
+        self.resume_with(())
+    }
+}
+
+impl<G: Coroutine + Unpin + ?Sized> Coroutine for &mut G {
+    type Yield = G::Yield;
+    type Resume = G::Resume;
+    type Return = G::Return;
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: Self::Resume,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        G::resume_with(Pin::new(&mut **self), arg)
+    }
+}
+
+impl<G: Coroutine + Unpin + ?Sized> Coroutine for Box<G> {
+    type Yield = G::Yield;
+    type Resume = G::Resume;
+    type Return = G::Return;
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: Self::Resume,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        G::resume_with(Pin::new(&mut **self), arg)
+    }
+}
+
+impl<P> Coroutine for Pin<P>
+where
+    P: DerefMut,
+    P::Target: Coroutine,
+{
+    type Yield = <P::Target as Coroutine>::Yield;
+    type Resume = <P::Target as Coroutine>::Resume;
+    type Return = <P::Target as Coroutine>::Return;
+
+    fn resume_with(
+        self: Pin<&mut Self>,
+        arg: Self::Resume,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        // Safety: The outer `Pin` is never moved out of. Only the inner
+        // pointer is used, and it pins its own target.
+        unsafe { self.get_unchecked_mut() }
+            .as_mut()
+            .resume_with(arg)
+    }
+}
+
+impl<G: AsyncCoroutine + Unpin + ?Sized> AsyncCoroutine for &mut G {
+    type Yield = G::Yield;
+    type Resume = G::Resume;
+    type Return = G::Return;
+
+    fn poll_resume(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        arg: Self::Resume,
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        G::poll_resume(Pin::new(&mut **self), cx, arg)
+    }
+}
+
+impl<G: AsyncCoroutine + Unpin + ?Sized> AsyncCoroutine for Box<G> {
+    type Yield = G::Yield;
+    type Resume = G::Resume;
+    type Return = G::Return;
+
+    fn poll_resume(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        arg: Self::Resume,
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        G::poll_resume(Pin::new(&mut **self), cx, arg)
+    }
+}
+
+impl<P> AsyncCoroutine for Pin<P>
+where
+    P: DerefMut,
+    P::Target: AsyncCoroutine,
+{
+    type Yield = <P::Target as AsyncCoroutine>::Yield;
+    type Resume = <P::Target as AsyncCoroutine>::Resume;
+    type Return = <P::Target as AsyncCoroutine>::Return;
+
+    fn poll_resume(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        arg: Self::Resume,
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        // Safety: The outer `Pin` is never moved out of. Only the inner
+        // pointer is used, and it pins its own target.
+        unsafe { self.get_unchecked_mut() }
+            .as_mut()
+            .poll_resume(cx, arg)
+    }
+}
+
+/// An object-safe shorthand for a [`Coroutine`] with the given types.
+///
+/// This is implemented for every `Coroutine`. It exists so that trait objects
+/// can be written with type parameters, as `dyn DynCoroutine<Y, R, C>`, instead
+/// of spelling out each associated type. A trait object still has every method
+/// of `Coroutine`, since that is a supertrait.
+///
+/// Generators from different engines (or with different producers) can be
+/// stored side by side as a [`BoxCoroutine`], which every engine's `Gen` can be
+/// turned into with its `boxed` method.
+///
+/// ```rust
+/// use genawaiter::{
+///     ops::BoxCoroutine,
+///     rc,
+///     stack::let_gen_using,
+///     sync,
+///     GeneratorState,
+/// };
+///
+/// let_gen_using!(stack_gen, |mut co| async move { co.yield_(3).await });
+/// let mut gens: Vec<BoxCoroutine<'_, i32>> = vec![
+///     rc::Gen::new(|mut co| async move { co.yield_(1).await }).boxed(),
+///     sync::Gen::new(|mut co| async move { co.yield_(2).await }).boxed(),
+///     Box::pin(stack_gen),
+/// ];
+/// for (gen, expected) in gens.iter_mut().zip(1..) {
+///     assert_eq!(
+///         gen.as_mut().resume_with(()),
+///         GeneratorState::Yielded(expected)
+///     );
+/// }
+/// ```
+pub trait DynCoroutine<Y, R = (), C = ()>:
+    Coroutine<Yield = Y, Resume = R, Return = C>
+{
+}
+
+impl<G, Y, R, C> DynCoroutine<Y, R, C> for G where
+    G: Coroutine<Yield = Y, Resume = R, Return = C> + ?Sized
+{
+}
+
+/// A boxed, type-erased [`Coroutine`], which can borrow data for the lifetime
+/// `'a`.
+///
+/// It is pinned, so it can hold generators which are not `Unpin`, such as an
+/// [`inline::Gen`](../inline/struct.Gen.html). See [`DynCoroutine`] for an
+/// example.
+pub type BoxCoroutine<'a, Y, R = (), C = ()> = Pin<Box<dyn DynCoroutine<Y, R, C> + 'a>>;
+
+/// The result of a generator resumption.
+///
+/// This is modeled after the stdlib's nightly-only
+/// [`std::ops::GeneratorState`].
+#[derive(PartialEq, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub enum GeneratorState<Y, R> {
+    /// The generator suspended with a value.
+    Yielded(Y),
+
+    /// The generator completed with a return value.
+    Complete(R),
+}
+
+/// One input of a combinator. Once the input completes, it is dropped, and its
+/// completion value is held here until every other input has completed too.
+enum Slot<C, T> {
+    Running(C),
+    Complete(T),
+    Taken,
+}
+
+impl<C, T> Slot<C, T> {
+    fn is_running(&self) -> bool {
+        match self {
+            Slot::Running(_) => true,
+            Slot::Complete(_) | Slot::Taken => false,
+        }
+    }
+
+    fn take_return(&mut self) -> T {
+        match mem::replace(self, Slot::Taken) {
+            Slot::Complete(value) => value,
+            Slot::Running(_) | Slot::Taken => {
+                panic!("a generator combinator was resumed after it completed")
+            }
+        }
+    }
+}
+
+impl<C: Coroutine + Unpin> Slot<C, C::Return> {
+    /// Resumes the coroutine if it is still running. Returns `None` if it has
+    /// completed, either just now or previously.
+    fn resume_with(&mut self, arg: C::Resume) -> Option<C::Yield> {
+        let coroutine = match self {
+            Slot::Running(coroutine) => coroutine,
+            Slot::Complete(_) | Slot::Taken => return None,
+        };
+        match Pin::new(coroutine).resume_with(arg) {
+            GeneratorState::Yielded(value) => Some(value),
+            GeneratorState::Complete(value) => {
+                *self = Slot::Complete(value);
+                None
+            }
+        }
+    }
+}
+
+impl<G: AsyncCoroutine<Resume = ()> + Unpin> Slot<G, G::Return> {
+    /// Polls the generator if it is still running. Returns `Ready(None)` if it
+    /// has completed, either just now or previously.
+    fn poll_resume(&mut self, cx: &mut Context<'_>) -> Poll<Option<G::Yield>> {
+        let generator = match self {
+            Slot::Running(generator) => generator,
+            Slot::Complete(_) | Slot::Taken => return Poll::Ready(None),
+        };
+        match Pin::new(generator).poll_resume(cx, ()) {
+            Poll::Ready(GeneratorState::Yielded(value)) => Poll::Ready(Some(value)),
+            Poll::Ready(GeneratorState::Complete(value)) => {
+                *self = Slot::Complete(value);
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inline,
+        ops::{
+            interleave,
+            AsyncCoroutine,
+            BoxCoroutine,
+            Coroutine,
+            Generator,
+            GeneratorState,
+        },
+        rc,
+        stack::let_gen_using,
+        sync,
+        thread,
+    };
+    use futures::executor;
+    use std::{future, pin::Pin};
+
+    fn resume<C: Coroutine<Resume = ()> + Unpin>(
+        c: &mut C,
+    ) -> GeneratorState<C::Yield, C::Return> {
+        Pin::new(c).resume_with(())
+    }
+
+    #[test]
+    fn pointers() {
+        let mut gen = rc::Gen::new(|mut co| {
+            async move {
+                co.yield_(1).await;
+                co.yield_(2).await;
+                co.yield_(3).await;
+            }
+        });
+        assert_eq!(resume(&mut &mut gen), GeneratorState::Yielded(1));
+
+        let mut boxed = Box::new(gen);
+        assert_eq!(resume(&mut boxed), GeneratorState::Yielded(2));
+
+        let mut pinned = Pin::new(boxed);
+        assert_eq!(Pin::new(&mut pinned).resume(), GeneratorState::Yielded(3));
+    }
+
+    #[test]
+    fn async_generic_over_engines() {
+        async fn sum<G>(mut gen: G) -> i32
+        where
+            G: AsyncCoroutine<Yield = i32, Resume = (), Return = ()> + Unpin,
+        {
+            let mut total = 0;
+            loop {
+                let next = future::poll_fn(|cx| Pin::new(&mut gen).poll_resume(cx, ()));
+                match next.await {
+                    GeneratorState::Yielded(n) => total += n,
+                    GeneratorState::Complete(()) => return total,
+                }
+            }
+        }
+
+        async fn produce_inline(mut co: inline::Co<'_, i32>) {
+            co.yield_(4).await;
+        }
+
+        let_gen_using!(stack_gen, |mut co| async move { co.yield_(3).await });
+        let total = executor::block_on(async {
+            sum(rc::Gen::new(|mut co| async move { co.yield_(1).await })).await
+                + sum(Box::new(sync::Gen::new(|mut co| {
+                    async move {
+                        co.yield_(2).await;
+                    }
+                })))
+                .await
+                + sum(stack_gen).await
+                + sum(Box::pin(inline::Gen::new(produce_inline))).await
+        });
+        assert_eq!(total, 10);
+    }
+
+    #[test]
+    fn box_every_engine() {
+        async fn yield_four(mut co: inline::Co<'_, i32>) {
+            co.yield_(4).await;
+        }
+
+        let_gen_using!(stack_gen, |mut co| async move { co.yield_(3).await });
+        let mut gens: Vec<BoxCoroutine<'_, i32>> = vec![
+            rc::Gen::new(|mut co| async move { co.yield_(1).await }).boxed(),
+            sync::Gen::new(|mut co| async move { co.yield_(2).await }).boxed(),
+            Box::pin(stack_gen),
+            inline::Gen::new(yield_four).boxed(),
+            thread::Gen::new(|mut co| co.yield_(5)).boxed(),
+        ];
+        for (gen, expected) in gens.iter_mut().zip(1..) {
+            assert_eq!(gen.as_mut().resume(), GeneratorState::Yielded(expected));
+            assert_eq!(gen.as_mut().resume(), GeneratorState::Complete(()));
+        }
+    }
+
+    #[test]
+    fn boxed_in_combinator() {
+        async fn odds(mut co: inline::Co<'_, i32>) {
+            co.yield_(1).await;
+            co.yield_(3).await;
+        }
+
+        let a = inline::Gen::new(odds);
+        let b = rc::Gen::new(|mut co| async move { co.yield_(2).await });
+        let result: Vec<_> = interleave(vec![a.boxed(), b.boxed()]).collect();
+        assert_eq!(result, [1, 2, 3]);
+    }
+}
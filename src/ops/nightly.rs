@@ -0,0 +1,102 @@
+use crate::ops::{Coroutine, GeneratorState};
+use std::{
+    marker::PhantomData,
+    ops::{self, CoroutineState},
+    pin::Pin,
+};
+
+/// Wraps one of the stdlib's nightly-only coroutines, so it can be used as a
+/// [`Coroutine`].
+///
+/// The result passes each resume argument through to the inner coroutine, and
+/// converts its `CoroutineState` into a `GeneratorState`. This lets code written
+/// against this crate's traits accept native coroutines, so a codebase can
+/// migrate from one to the other gradually.
+///
+/// This requires the `nightly` feature.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(coroutines, yield_expr)]
+/// # fn main() {
+/// use genawaiter::{ops::from_std, GeneratorState};
+///
+/// let mut gen = from_std(Box::pin(
+///     #[coroutine]
+///     || {
+///         yield 1;
+///         "done"
+///     },
+/// ));
+/// assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+/// assert_eq!(gen.resume(), GeneratorState::Complete("done"));
+/// # }
+/// ```
+pub fn from_std<C: ops::Coroutine<R>, R>(coroutine: C) -> FromStd<C, R> {
+    FromStd {
+        coroutine,
+        resume: PhantomData,
+    }
+}
+
+/// A [`Coroutine`] which wraps one of the stdlib's coroutines.
+///
+/// This is created by [`from_std`]. See its documentation for more.
+#[allow(clippy::module_name_repetitions)]
+pub struct FromStd<C, R> {
+    coroutine: C,
+    resume: PhantomData<fn(R)>,
+}
+
+impl<C: ops::Coroutine<R> + Unpin, R> FromStd<C, R> {
+    /// Resumes the inner coroutine with the resume argument `arg`.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(&mut self, arg: R) -> GeneratorState<C::Yield, C::Return> {
+        Pin::new(&mut self.coroutine).resume(arg).into()
+    }
+}
+
+impl<C: ops::Coroutine<()> + Unpin> FromStd<C, ()> {
+    /// Resumes the inner coroutine.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume(&mut self) -> GeneratorState<C::Yield, C::Return> {
+        self.resume_with(())
+    }
+}
+
+impl<C: ops::Coroutine<R>, R> Coroutine for FromStd<C, R> {
+    type Yield = C::Yield;
+    type Resume = R;
+    type Return = C::Return;
+
+    fn resume_with(
+        self: Pin<&mut Self>,
+        arg: R,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        // Safety: The coroutine is pinned structurally. It is never moved out of
+        // `self`, and `FromStd` is only `Unpin` if the coroutine is.
+        let coroutine = unsafe { self.map_unchecked_mut(|s| &mut s.coroutine) };
+        coroutine.resume(arg).into()
+    }
+}
+
+impl<Y, R> From<GeneratorState<Y, R>> for CoroutineState<Y, R> {
+    fn from(state: GeneratorState<Y, R>) -> Self {
+        match state {
+            GeneratorState::Yielded(y) => CoroutineState::Yielded(y),
+            GeneratorState::Complete(r) => CoroutineState::Complete(r),
+        }
+    }
+}
+
+impl<Y, R> From<CoroutineState<Y, R>> for GeneratorState<Y, R> {
+    fn from(state: CoroutineState<Y, R>) -> Self {
+        match state {
+            CoroutineState::Yielded(y) => GeneratorState::Yielded(y),
+            CoroutineState::Complete(r) => GeneratorState::Complete(r),
+        }
+    }
+}
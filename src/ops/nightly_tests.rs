@@ -0,0 +1,50 @@
+// These tests can't be parsed on non-nightly compilers, so move them to a
+// separate file.
+
+use crate::{
+    ops::{from_std, zip},
+    rc::{Co, Gen},
+    GeneratorState,
+};
+use std::ops::CoroutineState;
+
+#[test]
+fn resume_args() {
+    let mut gen = from_std(
+        #[coroutine]
+        |a: i32| {
+            let b = yield a;
+            a + b
+        },
+    );
+    assert_eq!(gen.resume_with(1), GeneratorState::Yielded(1));
+    assert_eq!(gen.resume_with(2), GeneratorState::Complete(3));
+}
+
+#[test]
+fn mixed_with_gen() {
+    async fn produce(mut co: Co<i32>) {
+        co.yield_(10).await;
+    }
+
+    let native = Box::pin(
+        #[coroutine]
+        || {
+            yield 1;
+        },
+    );
+    let mut zipped = zip(from_std(native), Gen::new(produce));
+    assert_eq!(
+        zipped.resume(),
+        GeneratorState::Yielded((Some(1), Some(10)))
+    );
+    assert_eq!(zipped.resume(), GeneratorState::Complete(((), ())));
+}
+
+#[test]
+fn convert_state() {
+    let state: CoroutineState<i32, ()> = GeneratorState::Yielded(1).into();
+    assert_eq!(state, CoroutineState::Yielded(1));
+    let state: GeneratorState<(), &str> = CoroutineState::Complete("x").into();
+    assert_eq!(state, GeneratorState::Complete("x"));
+}
@@ -0,0 +1,161 @@
+use crate::ops::{AsyncCoroutine, GeneratorState, Slot};
+use std::{
+    future::{self, Future},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Combines several async generators into one which yields values from whichever
+/// generator is ready first.
+///
+/// Each time the result is resumed, the inputs are polled in turn, starting after
+/// the one which yielded most recently (so a busy generator cannot starve the
+/// others). The first value any of them yields is yielded. The result completes
+/// once every input has completed, with their completion values in the same order
+/// as the inputs.
+///
+/// The inputs can be the async generators of any engine, e.g., [`rc::Gen`] or
+/// [`sync::Gen`].
+///
+/// [`rc::Gen`]: crate::rc::Gen
+/// [`sync::Gen`]: crate::sync::Gen
+///
+/// # Example
+///
+/// ```rust
+/// # async fn feature_gate() {
+/// use genawaiter::{
+///     ops::select,
+///     rc::{Co, Gen},
+///     GeneratorState,
+/// };
+///
+/// async fn source(name: &'static str, mut co: Co<&'static str>) -> usize {
+///     co.yield_(name).await;
+///     name.len()
+/// }
+///
+/// let sources = vec!["click", "key"].into_iter();
+/// let mut events = select(sources.map(|n| Gen::new(move |co| source(n, co))));
+/// assert_eq!(
+///     events.async_resume().await,
+///     GeneratorState::Yielded("click")
+/// );
+/// assert_eq!(events.async_resume().await, GeneratorState::Yielded("key"));
+/// assert_eq!(
+///     events.async_resume().await,
+///     GeneratorState::Complete(vec![5, 3])
+/// );
+/// # }
+/// ```
+pub fn select<G, I>(generators: I) -> Select<G>
+where
+    I: IntoIterator<Item = G>,
+    G: AsyncCoroutine<Resume = ()> + Unpin,
+{
+    Select {
+        slots: generators.into_iter().map(Slot::Running).collect(),
+        next: 0,
+    }
+}
+
+/// An async generator which yields from whichever of several async generators is
+/// ready first.
+///
+/// This is created by [`select`]. See its documentation for more.
+pub struct Select<G: AsyncCoroutine> {
+    slots: Vec<Slot<G, G::Return>>,
+    next: usize,
+}
+
+// The inputs are only ever pinned through `Pin::new`, which requires `Unpin`, and
+// the completion values are never pinned at all.
+impl<G: AsyncCoroutine> Unpin for Select<G> {}
+
+impl<G: AsyncCoroutine<Resume = ()> + Unpin> Select<G> {
+    /// Resumes the inputs until one of them yields a value.
+    ///
+    /// If every input pauses without yielding, `Poll::Pending` is returned. If
+    /// any input yields a value, `Poll::Ready(Yielded)` is returned. Otherwise,
+    /// `Poll::Ready(Completed)` is returned.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn async_resume(
+        &mut self,
+    ) -> impl Future<Output = GeneratorState<G::Yield, Vec<G::Return>>> + '_ {
+        future::poll_fn(move |cx| Pin::new(&mut *self).poll_resume(cx, ()))
+    }
+}
+
+impl<G: AsyncCoroutine<Resume = ()> + Unpin> AsyncCoroutine for Select<G> {
+    type Yield = G::Yield;
+    type Resume = ();
+    type Return = Vec<G::Return>;
+
+    fn poll_resume(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        (): (),
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        let len = self.slots.len();
+        for offset in 0..len {
+            let index = (self.next + offset) % len;
+            if let Poll::Ready(Some(value)) = self.slots[index].poll_resume(cx) {
+                self.next = (index + 1) % len;
+                return Poll::Ready(GeneratorState::Yielded(value));
+            }
+        }
+
+        if self.slots.iter().any(Slot::is_running) {
+            Poll::Pending
+        } else {
+            let returns = self.slots.iter_mut().map(Slot::take_return).collect();
+            Poll::Ready(GeneratorState::Complete(returns))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::select,
+        rc::{Co, Gen},
+        testing::SlowFuture,
+        GeneratorState,
+    };
+    use futures::executor::block_on;
+    use std::{future::Future, pin::Pin};
+
+    #[test]
+    fn first_to_yield() {
+        async fn slow(mut co: Co<&'static str>) -> i32 {
+            SlowFuture::new().await;
+            co.yield_("slow").await;
+            1
+        }
+
+        async fn fast(mut co: Co<&'static str>) -> i32 {
+            co.yield_("fast 1").await;
+            co.yield_("fast 2").await;
+            2
+        }
+
+        block_on(async {
+            type Boxed = Pin<Box<dyn Future<Output = i32>>>;
+            let gens = vec![
+                Gen::new(|co| Box::pin(slow(co)) as Boxed),
+                Gen::new(|co| Box::pin(fast(co)) as Boxed),
+            ];
+            let mut gen = select(gens);
+            let mut items = Vec::new();
+            let returns = loop {
+                match gen.async_resume().await {
+                    GeneratorState::Yielded(x) => items.push(x),
+                    GeneratorState::Complete(returns) => break returns,
+                }
+            };
+            assert_eq!(items, ["fast 1", "fast 2", "slow"]);
+            assert_eq!(returns, [1, 2]);
+        });
+    }
+}
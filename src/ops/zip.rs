@@ -0,0 +1,296 @@
+use crate::ops::{Coroutine, GeneratorState, Slot};
+use std::pin::Pin;
+
+/// Combines two coroutines into one which yields their values in pairs.
+///
+/// Each time the result is resumed, both inputs are resumed with a clone of the
+/// resume argument. If one input completes before the other, `None` is yielded in
+/// its place until the other completes as well. The result completes with the
+/// completion values of both inputs.
+///
+/// To give each input its own resume argument, use [`zip_split`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "proc_macro")]
+/// # fn feature_gate() {
+/// use genawaiter::{ops::zip, rc::gen, yield_, GeneratorState};
+///
+/// let mut zipped = zip(
+///     gen!({
+///         yield_!(1);
+///         "a"
+///     }),
+///     gen!({
+///         yield_!(10);
+///         yield_!(20);
+///         "b"
+///     }),
+/// );
+/// assert_eq!(
+///     zipped.resume(),
+///     GeneratorState::Yielded((Some(1), Some(10)))
+/// );
+/// assert_eq!(zipped.resume(), GeneratorState::Yielded((None, Some(20))));
+/// assert_eq!(zipped.resume(), GeneratorState::Complete(("a", "b")));
+/// # }
+/// ```
+pub fn zip<A, B>(a: A, b: B) -> Zip<A, B>
+where
+    A: Coroutine + Unpin,
+    B: Coroutine<Resume = A::Resume> + Unpin,
+    A::Resume: Clone,
+{
+    Zip(Pair::new(a, b))
+}
+
+/// Combines two coroutines into one which yields their values in pairs.
+///
+/// This works like [`zip`], except that the resume argument is a pair, and each
+/// input receives its own half of it. If one input has already completed, its half
+/// of the resume argument is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "proc_macro")]
+/// # fn feature_gate() {
+/// use genawaiter::{ops::zip_split, rc::gen, yield_, GeneratorState};
+///
+/// let mut zipped = zip_split(
+///     gen!({
+///         let n: i32 = yield_!(());
+///         n
+///     }),
+///     gen!({
+///         let s: &str = yield_!(());
+///         s
+///     }),
+/// );
+/// assert_eq!(
+///     zipped.resume_with((0, "")),
+///     GeneratorState::Yielded((Some(()), Some(()))),
+/// );
+/// assert_eq!(
+///     zipped.resume_with((5, "five")),
+///     GeneratorState::Complete((5, "five")),
+/// );
+/// # }
+/// ```
+pub fn zip_split<A, B>(a: A, b: B) -> ZipSplit<A, B>
+where
+    A: Coroutine + Unpin,
+    B: Coroutine + Unpin,
+{
+    ZipSplit(Pair::new(a, b))
+}
+
+/// A coroutine which resumes two coroutines in lockstep.
+///
+/// This is created by [`zip`]. See its documentation for more.
+pub struct Zip<A: Coroutine, B: Coroutine>(Pair<A, B>);
+
+/// A coroutine which resumes two coroutines in lockstep, with separate resume
+/// arguments.
+///
+/// This is created by [`zip_split`]. See its documentation for more.
+#[allow(clippy::module_name_repetitions)]
+pub struct ZipSplit<A: Coroutine, B: Coroutine>(Pair<A, B>);
+
+type ZipState<A, B> = GeneratorState<
+    (
+        Option<<A as Coroutine>::Yield>,
+        Option<<B as Coroutine>::Yield>,
+    ),
+    (<A as Coroutine>::Return, <B as Coroutine>::Return),
+>;
+
+struct Pair<A: Coroutine, B: Coroutine> {
+    a: Slot<A, A::Return>,
+    b: Slot<B, B::Return>,
+}
+
+impl<A: Coroutine + Unpin, B: Coroutine + Unpin> Pair<A, B> {
+    fn new(a: A, b: B) -> Self {
+        Self {
+            a: Slot::Running(a),
+            b: Slot::Running(b),
+        }
+    }
+
+    fn resume_with(&mut self, a_arg: A::Resume, b_arg: B::Resume) -> ZipState<A, B> {
+        let a = self.a.resume_with(a_arg);
+        let b = self.b.resume_with(b_arg);
+        if a.is_none() && b.is_none() {
+            GeneratorState::Complete((self.a.take_return(), self.b.take_return()))
+        } else {
+            GeneratorState::Yielded((a, b))
+        }
+    }
+}
+
+// The inputs are only ever pinned through `Pin::new`, which requires `Unpin`, and
+// the completion values are never pinned at all.
+impl<A: Coroutine, B: Coroutine> Unpin for Zip<A, B> {}
+impl<A: Coroutine, B: Coroutine> Unpin for ZipSplit<A, B> {}
+
+impl<A, B> Zip<A, B>
+where
+    A: Coroutine + Unpin,
+    B: Coroutine<Resume = A::Resume> + Unpin,
+    A::Resume: Clone,
+{
+    /// Resumes both inputs, passing each a clone of `arg`.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(&mut self, arg: A::Resume) -> ZipState<A, B> {
+        self.0.resume_with(arg.clone(), arg)
+    }
+}
+
+impl<A, B> Zip<A, B>
+where
+    A: Coroutine<Resume = ()> + Unpin,
+    B: Coroutine<Resume = ()> + Unpin,
+{
+    /// Resumes both inputs.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume(&mut self) -> ZipState<A, B> {
+        self.resume_with(())
+    }
+}
+
+impl<A, B> Coroutine for Zip<A, B>
+where
+    A: Coroutine + Unpin,
+    B: Coroutine<Resume = A::Resume> + Unpin,
+    A::Resume: Clone,
+{
+    type Yield = (Option<A::Yield>, Option<B::Yield>);
+    type Resume = A::Resume;
+    type Return = (A::Return, B::Return);
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: A::Resume,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume_with(&mut *self, arg)
+    }
+}
+
+impl<A: Coroutine + Unpin, B: Coroutine + Unpin> ZipSplit<A, B> {
+    /// Resumes both inputs, passing each its half of `arg`.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(&mut self, arg: (A::Resume, B::Resume)) -> ZipState<A, B> {
+        let (a_arg, b_arg) = arg;
+        self.0.resume_with(a_arg, b_arg)
+    }
+}
+
+impl<A: Coroutine + Unpin, B: Coroutine + Unpin> Coroutine for ZipSplit<A, B> {
+    type Yield = (Option<A::Yield>, Option<B::Yield>);
+    type Resume = (A::Resume, B::Resume);
+    type Return = (A::Return, B::Return);
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: (A::Resume, B::Resume),
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume_with(&mut *self, arg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::{zip, zip_split},
+        rc::{Co, Gen},
+        GeneratorState,
+    };
+
+    async fn count_to(n: i32, mut co: Co<i32>) -> &'static str {
+        for i in 1..=n {
+            co.yield_(i).await;
+        }
+        "done"
+    }
+
+    #[test]
+    fn zip_same_length() {
+        let mut zipped = zip(
+            Gen::new(|co| count_to(2, co)),
+            Gen::new(|co| count_to(2, co)),
+        );
+        assert_eq!(zipped.resume(), GeneratorState::Yielded((Some(1), Some(1))));
+        assert_eq!(zipped.resume(), GeneratorState::Yielded((Some(2), Some(2))));
+        assert_eq!(zipped.resume(), GeneratorState::Complete(("done", "done")));
+    }
+
+    #[test]
+    fn zip_different_lengths() {
+        let mut zipped = zip(
+            Gen::new(|co| count_to(3, co)),
+            Gen::new(|co| count_to(1, co)),
+        );
+        assert_eq!(zipped.resume(), GeneratorState::Yielded((Some(1), Some(1))));
+        assert_eq!(zipped.resume(), GeneratorState::Yielded((Some(2), None)));
+        assert_eq!(zipped.resume(), GeneratorState::Yielded((Some(3), None)));
+        assert_eq!(zipped.resume(), GeneratorState::Complete(("done", "done")));
+    }
+
+    #[test]
+    fn zip_broadcasts_resume_args() {
+        async fn echo(mut co: Co<String, String>) {
+            let mut s = co.yield_(String::new()).await;
+            loop {
+                s = co.yield_(s).await;
+            }
+        }
+
+        let mut zipped = zip(Gen::new(echo), Gen::new(echo));
+        zipped.resume_with("ignored".to_string());
+        assert_eq!(
+            zipped.resume_with("hi".to_string()),
+            GeneratorState::Yielded((Some("hi".to_string()), Some("hi".to_string()))),
+        );
+    }
+
+    #[test]
+    fn zip_split_resume_args() {
+        async fn sum(mut co: Co<i32, i32>) -> i32 {
+            let mut total = 0;
+            for _ in 0..2 {
+                total += co.yield_(total).await;
+            }
+            total
+        }
+
+        let mut zipped = zip_split(Gen::new(sum), Gen::new(sum));
+        assert_eq!(
+            zipped.resume_with((0, 0)),
+            GeneratorState::Yielded((Some(0), Some(0))),
+        );
+        assert_eq!(
+            zipped.resume_with((1, 10)),
+            GeneratorState::Yielded((Some(1), Some(10))),
+        );
+        assert_eq!(
+            zipped.resume_with((2, 20)),
+            GeneratorState::Complete((3, 30))
+        );
+    }
+
+    #[test]
+    #[should_panic = "resumed after it completed"]
+    fn resume_after_complete() {
+        let mut zipped = zip(
+            Gen::new(|co| count_to(0, co)),
+            Gen::new(|co| count_to(0, co)),
+        );
+        assert_eq!(zipped.resume(), GeneratorState::Complete(("done", "done")));
+        zipped.resume();
+    }
+}
@@ -0,0 +1,101 @@
+use crate::rc::{Co, Gen};
+use std::{future::Future, pin::Pin};
+
+/// This is a type alias for generators with a boxed future, which makes their
+/// type nameable. It's only really needed to help the compiler's type inference
+/// along.
+///
+/// The future may borrow data for the lifetime `'a`. Unlike
+/// [`sync::GenBoxed`](../sync/type.GenBoxed.html), the future does not need to
+/// be `Send`.
+#[allow(clippy::module_name_repetitions)]
+pub type GenBoxed<'a, Y, R = (), C = ()> =
+    Gen<Y, R, Pin<Box<dyn Future<Output = C> + 'a>>>;
+
+impl<'a, Y, R, C> GenBoxed<'a, Y, R, C> {
+    /// Creates a new generator with a boxed future, so it can be returned from
+    /// a function.
+    ///
+    /// This works exactly the same as [`Gen::new`](struct.Gen.html#method.new)
+    /// with an immediately boxed future.
+    ///
+    /// This method exists solely to help the compiler with type inference.
+    /// These two lines are equivalent, except that the compiler cannot infer
+    /// the correct type on the second line:
+    ///
+    /// ```compile_fail
+    /// # use genawaiter::rc::{Co, Gen, GenBoxed};
+    /// # use std::{future::Future, pin::Pin};
+    /// #
+    /// # async fn producer(co: Co<i32>) {
+    /// #     for n in (1..).step_by(2).take_while(|&n| n < 10) { co.yield_(n).await; }
+    /// # }
+    /// #
+    /// let _: GenBoxed<'static, i32> = Gen::new_boxed(|co| producer(co));
+    /// let _: GenBoxed<'static, i32> = Gen::new(|co| Box::pin(producer(co)));
+    /// ```
+    pub fn new_boxed<F>(producer: impl FnOnce(Co<Y, R>) -> F) -> Self
+    where
+        F: Future<Output = C> + 'a,
+    {
+        Self::new(|co| Box::pin(producer(co)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rc::{Gen, GenBoxed};
+    use std::rc::Rc;
+
+    struct Tree {
+        value: i32,
+        children: Vec<Tree>,
+    }
+
+    fn walk(tree: &Tree) -> GenBoxed<'_, i32> {
+        Gen::new_boxed(move |mut co| {
+            async move {
+                co.yield_(tree.value).await;
+                for child in &tree.children {
+                    for value in walk(child) {
+                        co.yield_(value).await;
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn borrows_local_data() {
+        let leaf = |value| {
+            Tree {
+                value,
+                children: Vec::new(),
+            }
+        };
+        let tree = Tree {
+            value: 1,
+            children: vec![
+                Tree {
+                    value: 2,
+                    children: vec![leaf(3), leaf(4)],
+                },
+                leaf(5),
+            ],
+        };
+
+        let values: Vec<_> = walk(&tree).into_iter().collect();
+        assert_eq!(values, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn need_not_be_send() {
+        let shared = Rc::new(10);
+        let mut gen: GenBoxed<'static, i32> = Gen::new_boxed(|mut co| {
+            async move {
+                co.yield_(*shared).await;
+            }
+        });
+        assert_eq!(gen.resume(), crate::GeneratorState::Yielded(10));
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
     core::{advance, async_advance, Airlock as _, Next},
-    ops::{Coroutine, GeneratorState},
+    ops::{AsyncCoroutine, BoxCoroutine, Coroutine, GeneratorState},
     rc::{engine::Airlock, Co},
 };
-use std::{future::Future, pin::Pin};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 /// This is a generator which stores its state on the heap.
 ///
@@ -46,6 +50,18 @@ impl<Y, R, F: Future> Gen<Y, R, F> {
         self.airlock.replace(Next::Resume(arg));
         advance(self.future.as_mut(), &self.airlock)
     }
+
+    /// Boxes this generator, so it can be stored alongside generators of other
+    /// types, as a [`BoxCoroutine`].
+    ///
+    /// [_See the `DynCoroutine` docs for examples._](../ops/trait.DynCoroutine.html)
+    #[must_use]
+    pub fn boxed<'a>(self) -> BoxCoroutine<'a, Y, R, F::Output>
+    where
+        Self: 'a,
+    {
+        Box::pin(self)
+    }
 }
 
 impl<Y, F: Future> Gen<Y, (), F> {
@@ -86,3 +102,21 @@ impl<Y, R, F: Future> Coroutine for Gen<Y, R, F> {
         Self::resume_with(&mut *self, arg)
     }
 }
+
+impl<Y, R, F: Future> AsyncCoroutine for Gen<Y, R, F> {
+    type Yield = Y;
+    type Resume = R;
+    type Return = F::Output;
+
+    fn poll_resume(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        arg: R,
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        self.airlock.replace(Next::Resume(arg));
+        let this = &mut *self;
+        let fut = async_advance(this.future.as_mut(), this.airlock.clone());
+        pin_mut!(fut);
+        fut.poll(cx)
+    }
+}
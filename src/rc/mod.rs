@@ -192,6 +192,30 @@ assert_eq!(gen.resume(), GeneratorState::Yielded(10));
 # }
 ```
 
+## Passing arguments to a producer function
+
+A producer function can accept arguments. Calling it captures them, and returns the
+producer. The attribute also accepts the resume type, as in
+`#[producer_fn(u8, resume = &str)]`.
+
+```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
+# use genawaiter::{rc::{producer_fn, Gen}, yield_, GeneratorState};
+#
+#[producer_fn(u8)]
+async fn odds_below(limit: u8) {
+    for n in (1..limit).step_by(2) {
+        yield_!(n);
+    }
+}
+
+let mut gen = Gen::new(odds_below(5));
+assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+assert_eq!(gen.resume(), GeneratorState::Yielded(3));
+# }
+```
+
 ## Defining a reusable producer closure
 
 ```rust
@@ -280,33 +304,157 @@ assert_eq!(gen.resume(), GeneratorState::Yielded(30));
 ```
 */
 
-pub use crate::rc::{engine::Co, generator::Gen};
+pub use crate::rc::{boxed::GenBoxed, engine::Co, generator::Gen};
 
 /// Creates a generator.
 ///
 /// This macro takes one argument, which is the body of the generator. It should
 /// contain one or more calls to the [`yield_!`] macro.
 ///
+/// The body can be preceded by the types of the generator, as in
+/// `gen!(yield i32, resume &str, return usize => { ... })`. Each one is
+/// optional.
+///
+/// To name the [`Co`] object, e.g., to pass it to a helper function, write the
+/// body like a closure, as in `gen!(|co| { ... })`.
+///
 /// # Examples
 ///
 /// [_See the module-level docs for examples._](.)
 #[cfg(feature = "proc_macro")]
 pub use genawaiter_macro::rc_gen as gen;
 
+/// Creates a generator which can only be resumed synchronously, e.g., as an
+/// `Iterator`.
+///
+/// This is like [`gen!`], except that the body cannot `.await` anything other
+/// than its own yields. A [`gen!`] which awaits another future panics when it
+/// is resumed, since nothing will wake that future. An `iter_gen!` turns that
+/// mistake into a compile error.
+///
+/// # Examples
+///
+/// ```rust
+/// use genawaiter::{rc::iter_gen, yield_};
+///
+/// let gen = iter_gen!({
+///     for n in 1..4 {
+///         yield_!(n * 10);
+///     }
+/// });
+/// assert_eq!(gen.into_iter().collect::<Vec<_>>(), [10, 20, 30]);
+/// ```
+///
+/// ```compile_fail
+/// use genawaiter::{rc::iter_gen, yield_};
+///
+/// async fn fetch() -> i32 {
+///     10
+/// }
+///
+/// let gen = iter_gen!({
+///     yield_!(fetch().await);
+/// });
+/// ```
+#[cfg(feature = "proc_macro")]
+pub use genawaiter_macro::rc_iter_gen as iter_gen;
+
+/// Creates a stream.
+///
+/// This macro takes one argument, which is the body of the stream. It should
+/// contain one or more calls to the [`yield_!`] macro. It evaluates to an
+/// `impl Stream<Item = Y>`, where `Y` is the type of the yielded values.
+///
+/// The body can await other futures. It can also consume another stream with
+/// `for await`, which works like a `for` loop, except that it awaits each item.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "futures03")]
+/// # fn feature_gate() {
+/// use futures::{executor::block_on_stream, Stream};
+/// use genawaiter::{rc::stream, yield_};
+///
+/// fn odds() -> impl Stream<Item = u8> {
+///     stream!({
+///         for n in (1..).step_by(2).take_while(|&n| n < 10) {
+///             yield_!(n);
+///         }
+///     })
+/// }
+///
+/// fn squares(source: impl Stream<Item = u8>) -> impl Stream<Item = u8> {
+///     stream!({
+///         for await n in source {
+///             yield_!(n * n);
+///         }
+///     })
+/// }
+///
+/// let items: Vec<_> = block_on_stream(Box::pin(squares(odds()))).collect();
+/// assert_eq!(items, [1, 9, 25, 49, 81]);
+/// # }
+/// ```
+#[cfg(all(feature = "proc_macro", feature = "futures03"))]
+pub use genawaiter_macro::rc_stream as stream;
+
+/// Creates a stream of `Result`s.
+///
+/// This is like [`stream!`], except that the body can use the `?` operator.
+/// Each `yield_!(value)` yields `Ok(value)`. If the body fails, the stream
+/// yields the error, and then ends. It evaluates to an
+/// `impl Stream<Item = Result<T, E>>`, which is also a `TryStream`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "futures03")]
+/// # fn feature_gate() {
+/// use futures::{executor::block_on_stream, Stream};
+/// use genawaiter::{rc::try_stream, yield_};
+/// use std::num::ParseIntError;
+///
+/// fn parse<'a>(
+///     words: &'a [&'a str],
+/// ) -> impl Stream<Item = Result<i32, ParseIntError>> + 'a {
+///     try_stream!({
+///         for word in words {
+///             yield_!(word.parse()?);
+///         }
+///     })
+/// }
+///
+/// let items: Vec<_> = block_on_stream(Box::pin(parse(&["1", "x", "3"]))).collect();
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items[0], Ok(1));
+/// assert!(items[1].is_err());
+/// # }
+/// ```
+#[cfg(all(feature = "proc_macro", feature = "futures03"))]
+pub use genawaiter_macro::rc_try_stream as try_stream;
+
 /// Turns a function into a producer, which can then be used to create a
 /// generator.
 ///
 /// The body of the function should contain one or more [`yield_!`] expressions.
 ///
+/// The attribute takes the yield type, and optionally the resume type, e.g.,
+/// `#[producer_fn(u8, resume = &str)]`. If the function has arguments, calling
+/// it returns a producer which has captured them.
+///
 /// # Examples
 ///
 /// [_See the module-level docs for examples._](.)
 #[cfg(feature = "proc_macro")]
 pub use genawaiter_proc_macro::rc_producer_fn as producer_fn;
 
+mod boxed;
 mod engine;
 mod generator;
 mod iterator;
+#[cfg(feature = "nightly")]
+mod nightly;
 #[cfg(feature = "futures03")]
 mod stream;
 
@@ -386,6 +534,7 @@ mod tests {
 
     #[test]
     #[should_panic(expected = "Co::yield_")]
+    #[allow(clippy::let_underscore_future)]
     fn multiple_yield_helpful_message() {
         async fn wrong(mut co: Co<i32>) {
             let _ = co.yield_(10);
@@ -398,6 +547,7 @@ mod tests {
 
     #[test]
     #[should_panic = "should have been dropped by now"]
+    #[allow(clippy::let_underscore_future)]
     fn escaped_co_helpful_message() {
         async fn shenanigans(co: Co<i32>) -> Co<i32> {
             co
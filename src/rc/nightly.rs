@@ -0,0 +1,81 @@
+use crate::{
+    ops::{AsyncCoroutine, Coroutine, GeneratorState},
+    rc::Gen,
+};
+use std::{
+    async_iter::AsyncIterator,
+    future::Future,
+    ops::{self, CoroutineState},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl<Y, R, F: Future> ops::Coroutine<R> for Gen<Y, R, F> {
+    type Yield = Y;
+    type Return = F::Output;
+
+    fn resume(self: Pin<&mut Self>, arg: R) -> CoroutineState<Y, F::Output> {
+        Coroutine::resume_with(self, arg).into()
+    }
+}
+
+impl<Y, F: Future<Output = ()>> AsyncIterator for Gen<Y, (), F> {
+    type Item = Y;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        match self.poll_resume(cx, ()) {
+            Poll::Ready(GeneratorState::Yielded(x)) => Poll::Ready(Some(x)),
+            Poll::Ready(GeneratorState::Complete(())) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rc::{Co, Gen},
+        testing::SlowFuture,
+    };
+    use std::{
+        async_iter::AsyncIterator,
+        future,
+        ops::{Coroutine, CoroutineState},
+        pin::Pin,
+    };
+
+    #[test]
+    fn std_coroutine() {
+        async fn produce(mut co: Co<i32, i32>) -> &'static str {
+            let n = co.yield_(10).await;
+            co.yield_(n + 1).await;
+            "done"
+        }
+
+        let mut gen = Gen::new(produce);
+        let mut gen = Pin::new(&mut gen);
+        assert_eq!(gen.as_mut().resume(0), CoroutineState::Yielded(10));
+        assert_eq!(gen.as_mut().resume(5), CoroutineState::Yielded(6));
+        assert_eq!(gen.as_mut().resume(0), CoroutineState::Complete("done"));
+    }
+
+    #[test]
+    fn async_iterator() {
+        async fn produce(mut co: Co<i32>) {
+            SlowFuture::new().await;
+            co.yield_(10).await;
+        }
+
+        let mut gen = Gen::new(produce);
+        let mut gen = Pin::new(&mut gen);
+        futures::executor::block_on(async {
+            let item = future::poll_fn(|cx| gen.as_mut().poll_next(cx)).await;
+            assert_eq!(item, Some(10));
+            let item = future::poll_fn(|cx| gen.as_mut().poll_next(cx)).await;
+            assert_eq!(item, None);
+        });
+    }
+}
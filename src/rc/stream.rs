@@ -1,4 +1,7 @@
-use crate::{ops::GeneratorState, rc::Gen};
+use crate::{
+    ops::{AsyncCoroutine, GeneratorState},
+    rc::Gen,
+};
 use futures_core::{
     task::{Context, Poll},
     Stream,
@@ -9,12 +12,10 @@ impl<Y, F: Future<Output = ()>> Stream for Gen<Y, (), F> {
     type Item = Y;
 
     fn poll_next(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let fut = self.async_resume();
-        pin_mut!(fut);
-        match fut.poll(cx) {
+        match self.poll_resume(cx, ()) {
             Poll::Ready(GeneratorState::Yielded(x)) => Poll::Ready(Some(x)),
             Poll::Ready(GeneratorState::Complete(())) => Poll::Ready(None),
             Poll::Pending => Poll::Pending,
@@ -1,5 +1,10 @@
 use crate::{core, core::Next};
-use std::{cell::UnsafeCell, ptr};
+use std::{
+    cell::UnsafeCell,
+    hint,
+    ptr,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 /// This type holds the value that is pending being returned from the generator.
 ///
@@ -10,13 +15,19 @@ use std::{cell::UnsafeCell, ptr};
 /// at each use site.
 pub struct Airlock<Y, R>(UnsafeCell<Next<Y, R>>);
 
+impl<Y, R> Airlock<Y, R> {
+    pub const fn new() -> Self {
+        Self(UnsafeCell::new(Next::Empty))
+    }
+}
+
 impl<Y, R> Default for Airlock<Y, R> {
     fn default() -> Self {
-        Self(UnsafeCell::new(Next::Empty))
+        Self::new()
     }
 }
 
-impl<'s, Y, R> core::Airlock for &'s Airlock<Y, R> {
+impl<Y, R> core::Airlock for Airlock<Y, R> {
     type Yield = Y;
     type Resume = R;
 
@@ -35,11 +46,89 @@ impl<'s, Y, R> core::Airlock for &'s Airlock<Y, R> {
     }
 }
 
+/// An airlock which can be shared between threads, so the generator which uses
+/// it is `Send`.
+///
+/// A [`Shelf`](super::Shelf) holds one of these if it is created with
+/// [`Shelf::new_send`](super::Shelf::new_send).
+///
+/// # Safety
+///
+/// Like [`Airlock`], this type is never exposed to user-land code, and never
+/// borrowed across a function call. Each access is guarded by the `busy` flag.
+/// The flag is only ever contended if a `Co` is misused from another thread, in
+/// which case the other thread spins until the current access is finished.
+pub struct SendAirlock<Y, R> {
+    busy: AtomicBool,
+    next: UnsafeCell<Next<Y, R>>,
+}
+
+// Safety: `with` is the only way to reach `next`. It sets `busy` (with `Acquire`)
+// before the access, and clears it (with `Release`) after, so no two accesses
+// overlap, and each one sees the writes of the one before. The closures passed
+// to `with` never call `with` again, so it cannot deadlock. The values inside
+// may be taken out on another thread, so they must be `Send`.
+unsafe impl<Y: Send, R: Send> Sync for SendAirlock<Y, R> {}
+
+impl<Y, R> SendAirlock<Y, R> {
+    pub const fn new() -> Self {
+        Self {
+            busy: AtomicBool::new(false),
+            next: UnsafeCell::new(Next::Empty),
+        }
+    }
+
+    fn with<T>(&self, f: impl FnOnce(*mut Next<Y, R>) -> T) -> T {
+        while self
+            .busy
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        let result = f(self.next.get());
+        self.busy.store(false, Ordering::Release);
+        result
+    }
+}
+
+impl<Y, R> Default for SendAirlock<Y, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Y, R> core::Airlock for SendAirlock<Y, R> {
+    type Yield = Y;
+    type Resume = R;
+
+    fn peek(&self) -> Next<(), ()> {
+        // Safety: This follows the safety rules above.
+        self.with(|next| unsafe { &*next }.without_values())
+    }
+
+    fn replace(
+        &self,
+        next: Next<Self::Yield, Self::Resume>,
+    ) -> Next<Self::Yield, Self::Resume> {
+        // Safety: This follows the safety rules above.
+        self.with(|inner| unsafe { ptr::replace(inner, next) })
+    }
+}
+
 /// This object lets you yield values from the generator by calling the `yield_`
 /// method.
 ///
 /// "Co" can stand for either _controller_ or _coroutine_, depending on how
 /// theoretical you are feeling.
 ///
+/// A generator which is `Send` takes a [`SendCo`] instead.
+///
 /// [_See the module-level docs for examples._](.)
 pub type Co<'y, Y, R = ()> = core::Co<&'y Airlock<Y, R>>;
+
+/// The `Co` of a generator whose shelf was created with
+/// [`Shelf::new_send`](super::Shelf::new_send).
+///
+/// [_See the module-level docs for examples._](.)
+pub type SendCo<'y, Y, R = ()> = core::Co<&'y SendAirlock<Y, R>>;
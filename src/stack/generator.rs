@@ -1,9 +1,16 @@
-use std::{future::Future, mem::MaybeUninit, pin::Pin, ptr};
+use std::{
+    future::Future,
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    pin::Pin,
+    ptr,
+    task::{Context, Poll},
+};
 
 use crate::{
-    core::{advance, async_advance, Airlock as _, Next},
-    ops::{Coroutine, GeneratorState},
-    stack::engine::{Airlock, Co},
+    core::{self, advance, async_advance, Next},
+    ops::{AsyncCoroutine, BoxCoroutine, Coroutine, GeneratorState},
+    stack::engine::{Airlock, SendAirlock},
 };
 
 /// This data structure holds the transient state of an executing generator.
@@ -11,51 +18,94 @@ use crate::{
 /// It's called "Shelf", rather than "State", to avoid confusion with the
 /// `GeneratorState` enum.
 ///
+/// A shelf holds one generator at a time. To reuse it for a new producer, call
+/// [`Gen::restart`] on the generator which holds it.
+///
+/// The last parameter is the airlock which passes values between the generator
+/// and its producer. By default, the generator is not `Send`. Create the shelf
+/// with [`Shelf::new_send`] to make it `Send`.
+///
 /// [_See the module-level docs for examples._](.)
-pub struct Shelf<Y, R, F: Future> {
-    airlock: Airlock<Y, R>,
+pub struct Shelf<Y, R, F: Future, A = Airlock<Y, R>> {
+    airlock: A,
     future: MaybeUninit<F>,
+    _types: PhantomData<fn(R) -> Y>,
 }
 
 impl<Y, R, F: Future> Shelf<Y, R, F> {
     /// Creates a new, empty `Shelf`.
     ///
+    /// This is a `const fn`, so a shelf can be created in a constant context,
+    /// such as a `thread_local!` with a `const` initializer.
+    ///
     /// [_See the module-level docs for examples._](.)
     #[must_use]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
-            airlock: Airlock::default(),
+            airlock: Airlock::new(),
             // Safety: The lifetime of the data is controlled by a `Gen`, which constructs
             // it in place, and holds a mutable reference right up until dropping it in
             // place. Thus, the data inside is pinned and can never be moved.
             future: MaybeUninit::uninit(),
+            _types: PhantomData,
         }
     }
 }
 
-impl<Y, R, F: Future> Default for Shelf<Y, R, F> {
+impl<Y, R, F: Future> Shelf<Y, R, F, SendAirlock<Y, R>> {
+    /// Creates a new, empty `Shelf`, for a generator which is `Send` (if its
+    /// types are), so it can be held across an `.await` in a multi-threaded
+    /// executor.
+    ///
+    /// The producer of the generator takes a [`SendCo`](super::SendCo). Every
+    /// value passed through it takes an uncontended atomic flag, so prefer
+    /// [`Shelf::new`] if the generator stays on one thread.
+    ///
+    /// [_See the module-level docs for examples._](.)
     #[must_use]
+    pub const fn new_send() -> Self {
+        Self {
+            airlock: SendAirlock::new(),
+            // Safety: See `Shelf::new`.
+            future: MaybeUninit::uninit(),
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<Y, R, F: Future> Default for Shelf<Y, R, F> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl<Y, R, F: Future> Default for Shelf<Y, R, F, SendAirlock<Y, R>> {
+    fn default() -> Self {
+        Self::new_send()
+    }
+}
+
 /// This is a generator which can be stack-allocated.
 ///
 /// [_See the module-level docs for examples._](.)
-pub struct Gen<'s, Y, R, F: Future> {
-    airlock: &'s Airlock<Y, R>,
+pub struct Gen<'s, Y, R, F: Future, A = Airlock<Y, R>> {
+    airlock: &'s A,
     future: Pin<&'s mut F>,
+    _types: PhantomData<fn(R) -> Y>,
 }
 
-impl<'s, Y, R, F: Future> Gen<'s, Y, R, F> {
+impl<'s, Y, R, F, A> Gen<'s, Y, R, F, A>
+where
+    F: Future,
+    A: core::Airlock<Yield = Y, Resume = R>,
+{
     /// Creates a new generator from a function.
     ///
     /// The state of the generator is stored in `shelf`, which will be pinned in
     /// place while this generator exists. The generator itself is movable,
     /// since it just holds a reference to the pinned state.
     ///
-    /// The function accepts a [`Co`] object, and returns a future. Every time
+    /// The function accepts a [`Co`](super::Co) object, and returns a future. Every time
     /// the generator is resumed, the future is polled. Each time the future is
     /// polled, it should do one of two things:
     ///
@@ -88,15 +138,18 @@ impl<'s, Y, R, F: Future> Gen<'s, Y, R, F> {
     /// let gen = unsafe { Gen::new(&mut shelf, producer) };
     /// ```
     pub unsafe fn new(
-        shelf: &'s mut Shelf<Y, R, F>,
-        producer: impl FnOnce(Co<'s, Y, R>) -> F,
+        shelf: &'s mut Shelf<Y, R, F, A>,
+        producer: impl FnOnce(core::Co<&'s A>) -> F,
     ) -> Self {
         // By splitting the mutable `shelf` into a shared `airlock` and a unique
         // pinned `future` reference we ensure the aliasing rules are not violated.
         let airlock = &shelf.airlock;
         // Safety: Initializes the future in-place using `ptr::write`, which is
         // the correct way to initialize a `MaybeUninit`
-        shelf.future.as_mut_ptr().write(producer(Co::new(airlock)));
+        shelf
+            .future
+            .as_mut_ptr()
+            .write(producer(core::Co::new(airlock)));
         // Safety: The `MaybeUninit` is initialized by now, so its safe to create
         // a reference to the future itself
         // NB: can be replaced by `MaybeUninit::get_mut` once stabilized
@@ -106,6 +159,74 @@ impl<'s, Y, R, F: Future> Gen<'s, Y, R, F> {
         Self {
             airlock,
             future: Pin::new_unchecked(init),
+            _types: PhantomData,
+        }
+    }
+
+    /// Replaces this generator with a new one, created from `producer`, in the
+    /// same [`Shelf`].
+    ///
+    /// The current future is dropped in place, even if it has not completed,
+    /// and the shelf is reset as though it were brand new. This lets a loop
+    /// reuse one shelf for a series of generators, instead of creating a new
+    /// shelf for each one. The new producer must return the same type of future
+    /// as the old one, e.g., by calling the same `async fn`.
+    ///
+    /// The producer is called before the current future is dropped, so if it
+    /// panics, the generator is left as it was.
+    ///
+    /// This is safe, since the new `Co` has the same lifetime as the old one,
+    /// which `Gen::new` has already ensured cannot outlive the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use genawaiter::{stack::{let_gen_using, Co}, GeneratorState};
+    /// #
+    /// async fn count_to(n: i32, mut co: Co<'_, i32>) {
+    ///     for i in 1..=n {
+    ///         co.yield_(i).await;
+    ///     }
+    /// }
+    ///
+    /// let_gen_using!(gen, |co| count_to(0, co));
+    /// for n in 1..=3 {
+    ///     gen.restart(|co| count_to(n, co));
+    ///     let total: i32 = gen.into_iter().sum();
+    ///     # assert_eq!(total, n * (n + 1) / 2);
+    /// }
+    /// ```
+    pub fn restart(&mut self, producer: impl FnOnce(core::Co<&'s A>) -> F) {
+        // Refills the shelf when dropped, so it is never left empty, even if
+        // dropping the old future panics.
+        struct Refill<F> {
+            slot: *mut F,
+            future: ManuallyDrop<F>,
+        }
+
+        impl<F> Drop for Refill<F> {
+            fn drop(&mut self) {
+                // Safety: `slot` points into the shelf, and the future there has
+                // just been dropped. `self.future` is never used again.
+                unsafe { ptr::write(self.slot, ManuallyDrop::take(&mut self.future)) };
+            }
+        }
+
+        // If the producer panics, the old future is left untouched.
+        let future = producer(core::Co::new(self.airlock));
+        self.airlock.replace(Next::Empty);
+        // Safety: The future is initialized, and is never moved. It is dropped in
+        // place, and `refill` then writes the new future in the same place, even
+        // if the drop unwinds. So the shelf holds a valid future again before
+        // anything can observe the gap.
+        unsafe {
+            let slot = self.future.as_mut().get_unchecked_mut();
+            let refill = Refill {
+                slot,
+                future: ManuallyDrop::new(future),
+            };
+            ptr::drop_in_place(slot);
+            drop(refill);
         }
     }
 
@@ -121,11 +242,23 @@ impl<'s, Y, R, F: Future> Gen<'s, Y, R, F> {
     /// [_See the module-level docs for examples._](.)
     pub fn resume_with(&mut self, arg: R) -> GeneratorState<Y, F::Output> {
         self.airlock.replace(Next::Resume(arg));
-        advance(self.future.as_mut(), &self.airlock)
+        advance(self.future.as_mut(), self.airlock)
+    }
+
+    /// Boxes this generator, so it can be stored alongside generators of other
+    /// types, as a [`BoxCoroutine`].
+    ///
+    /// [_See the `DynCoroutine` docs for examples._](../ops/trait.DynCoroutine.html)
+    #[must_use]
+    pub fn boxed<'a>(self) -> BoxCoroutine<'a, Y, R, F::Output>
+    where
+        Self: 'a,
+    {
+        Box::pin(self)
     }
 }
 
-impl<'s, Y, R, F: Future> Drop for Gen<'s, Y, R, F> {
+impl<Y, R, F: Future, A> Drop for Gen<'_, Y, R, F, A> {
     fn drop(&mut self) {
         // Safety: `future` itself is a `MaybeUninit`, which is guaranteed to be
         // initialized, because the only way to construct a `Gen` is with
@@ -140,7 +273,11 @@ impl<'s, Y, R, F: Future> Drop for Gen<'s, Y, R, F> {
     }
 }
 
-impl<'s, Y, F: Future> Gen<'s, Y, (), F> {
+impl<Y, F, A> Gen<'_, Y, (), F, A>
+where
+    F: Future,
+    A: core::Airlock<Yield = Y, Resume = ()>,
+{
     /// Resumes execution of the generator.
     ///
     /// If the generator yields a value, `Yielded` is returned. Otherwise,
@@ -166,7 +303,11 @@ impl<'s, Y, F: Future> Gen<'s, Y, (), F> {
     }
 }
 
-impl<'s, Y, R, F: Future> Coroutine for Gen<'s, Y, R, F> {
+impl<Y, R, F, A> Coroutine for Gen<'_, Y, R, F, A>
+where
+    F: Future,
+    A: core::Airlock<Yield = Y, Resume = R>,
+{
     type Yield = Y;
     type Resume = R;
     type Return = F::Output;
@@ -180,3 +321,25 @@ impl<'s, Y, R, F: Future> Coroutine for Gen<'s, Y, R, F> {
         this.resume_with(arg)
     }
 }
+
+impl<Y, R, F, A> AsyncCoroutine for Gen<'_, Y, R, F, A>
+where
+    F: Future,
+    A: core::Airlock<Yield = Y, Resume = R>,
+{
+    type Yield = Y;
+    type Resume = R;
+    type Return = F::Output;
+
+    fn poll_resume(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        arg: R,
+    ) -> Poll<GeneratorState<Self::Yield, Self::Return>> {
+        self.airlock.replace(Next::Resume(arg));
+        let this = &mut *self;
+        let fut = async_advance(this.future.as_mut(), this.airlock);
+        pin_mut!(fut);
+        fut.poll(cx)
+    }
+}
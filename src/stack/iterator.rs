@@ -1,21 +1,32 @@
-use crate::{ops::GeneratorState, stack::generator::Gen};
+use crate::{
+    core,
+    ops::GeneratorState,
+    stack::{engine::Airlock, generator::Gen},
+};
 use std::future::Future;
 
-impl<'s, Y, F: Future<Output = ()>> IntoIterator for Gen<'s, Y, (), F> {
+impl<'s, Y, F, A> IntoIterator for Gen<'s, Y, (), F, A>
+where
+    F: Future<Output = ()>,
+    A: core::Airlock<Yield = Y, Resume = ()>,
+{
     type Item = Y;
-    type IntoIter = IntoIter<'s, Y, F>;
+    type IntoIter = IntoIter<'s, Y, F, A>;
 
-    #[must_use]
     fn into_iter(self) -> Self::IntoIter {
         IntoIter { generator: self }
     }
 }
 
-pub struct IntoIter<'s, Y, F: Future<Output = ()>> {
-    generator: Gen<'s, Y, (), F>,
+pub struct IntoIter<'s, Y, F: Future<Output = ()>, A = Airlock<Y, ()>> {
+    generator: Gen<'s, Y, (), F, A>,
 }
 
-impl<'s, Y, F: Future<Output = ()>> Iterator for IntoIter<'s, Y, F> {
+impl<Y, F, A> Iterator for IntoIter<'_, Y, F, A>
+where
+    F: Future<Output = ()>,
+    A: core::Airlock<Yield = Y, Resume = ()>,
+{
     type Item = Y;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -26,20 +37,29 @@ impl<'s, Y, F: Future<Output = ()>> Iterator for IntoIter<'s, Y, F> {
     }
 }
 
-impl<'r, 's, Y, F: Future<Output = ()>> IntoIterator for &'r mut Gen<'s, Y, (), F> {
+#[allow(clippy::into_iter_without_iter)]
+impl<'r, 's, Y, F, A> IntoIterator for &'r mut Gen<'s, Y, (), F, A>
+where
+    F: Future<Output = ()>,
+    A: core::Airlock<Yield = Y, Resume = ()>,
+{
     type Item = Y;
-    type IntoIter = MutIntoIter<'r, 's, Y, F>;
+    type IntoIter = MutIntoIter<'r, 's, Y, F, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         MutIntoIter { generator: self }
     }
 }
 
-pub struct MutIntoIter<'r, 's, Y, F: Future<Output = ()>> {
-    generator: &'r mut Gen<'s, Y, (), F>,
+pub struct MutIntoIter<'r, 's, Y, F: Future<Output = ()>, A = Airlock<Y, ()>> {
+    generator: &'r mut Gen<'s, Y, (), F, A>,
 }
 
-impl<'r, 's, Y, F: Future<Output = ()>> Iterator for MutIntoIter<'r, 's, Y, F> {
+impl<Y, F, A> Iterator for MutIntoIter<'_, '_, Y, F, A>
+where
+    F: Future<Output = ()>,
+    A: core::Airlock<Yield = Y, Resume = ()>,
+{
     type Item = Y;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -43,6 +43,7 @@ mod tests {
     /// This test proves that `Gen::new` is actually unsafe.
     #[test]
     #[ignore = "compile-only test"]
+    #[allow(clippy::let_underscore_future)]
     fn unsafety() {
         async fn shenanigans(co: Co<'_, i32>) -> Co<'_, i32> {
             co
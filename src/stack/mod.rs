@@ -178,6 +178,30 @@ assert_eq!(gen.resume(), GeneratorState::Yielded(10));
 # }
 ```
 
+## Passing arguments to a producer function
+
+A producer function can accept arguments. Calling it captures them, and returns the
+producer. The attribute also accepts the resume type, as in
+`#[producer_fn(u8, resume = &str)]`.
+
+```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
+# use genawaiter::{stack::{let_gen_using, producer_fn}, yield_, GeneratorState};
+#
+#[producer_fn(u8)]
+async fn odds_below(limit: u8) {
+    for n in (1..limit).step_by(2) {
+        yield_!(n);
+    }
+}
+
+let_gen_using!(gen, odds_below(5));
+assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+assert_eq!(gen.resume(), GeneratorState::Yielded(3));
+# }
+```
+
 ## Using the low-level API
 
 You can define an `async fn` directly, instead of relying on the `gen!` or `producer!`
@@ -247,10 +271,43 @@ assert_eq!(gen.resume(), GeneratorState::Yielded(10));
 assert_eq!(gen.resume(), GeneratorState::Yielded(20));
 assert_eq!(gen.resume(), GeneratorState::Yielded(30));
 ```
+
+## Holding a generator across an `.await`
+
+By default, a stack generator is not `Send`. Create it with [`let_send_gen!`] or
+[`let_send_gen_using!`] instead, and it can be held across an `.await` in a
+multi-threaded executor, or moved to another thread. A producer for such a generator
+takes a [`SendCo`].
+
+[`let_send_gen!`]: macro.let_send_gen.html
+[`let_send_gen_using!`]: macro.let_send_gen_using.html
+
+```rust
+# use genawaiter::{stack::{let_send_gen_using, SendCo}, GeneratorState};
+#
+async fn countdown(mut co: SendCo<'_, i32>) {
+    for n in (1..=3).rev() {
+        co.yield_(n).await;
+    }
+}
+
+fn assert_send<T: Send>(task: T) -> T {
+    task
+}
+
+let task = assert_send(async {
+    let_send_gen_using!(gen, countdown);
+    let first = gen.resume();
+    std::future::ready(()).await;
+    (first, gen.resume())
+});
+# let state = futures::executor::block_on(task);
+# assert_eq!(state, (GeneratorState::Yielded(3), GeneratorState::Yielded(2)));
+```
 */
 
 pub use crate::stack::{
-    engine::Co,
+    engine::{Co, SendCo},
     generator::{Gen, Shelf},
 };
 
@@ -265,7 +322,10 @@ pub use crate::stack::{
 /// ```
 ///
 /// The second argument is the body of the generator. It should contain one or
-/// more calls to the [`yield_!`] macro.
+/// more calls to the [`yield_!`] macro. The body can be preceded by the types
+/// of the generator, as in `let_gen!(gen, yield i32, resume &str => { ... })`.
+/// To name the [`Co`] object, write the body like a closure, as in
+/// `let_gen!(gen, |co| { ... })`.
 ///
 /// This macro is a shortcut for creating both a generator and its backing state
 /// (called a [`Shelf`](struct.Shelf.html)). If you (or your IDE) dislike
@@ -315,11 +375,40 @@ pub use genawaiter_macro::stack_let_gen as let_gen;
 /// [_See the module-level docs for examples._](.)
 pub use genawaiter_macro::stack_let_gen_using as let_gen_using;
 
+/// Creates a generator which is `Send`.
+///
+/// This works like [`let_gen!`](macro.let_gen.html), except that the generator
+/// can be held across an `.await` in a multi-threaded executor, or moved to
+/// another thread. Passing each value through it takes an atomic flag, so
+/// prefer `let_gen!` when the generator stays on one thread.
+///
+/// # Examples
+///
+/// [_See the module-level docs for examples._](.)
+#[cfg(feature = "proc_macro")]
+pub use genawaiter_macro::stack_let_send_gen as let_send_gen;
+
+/// Creates a generator which is `Send`, using a producer defined elsewhere.
+///
+/// This works like [`let_gen_using!`](macro.let_gen_using.html), except that
+/// the generator can be held across an `.await` in a multi-threaded executor,
+/// or moved to another thread. The producer takes a [`SendCo`] instead of a
+/// [`Co`].
+///
+/// # Examples
+///
+/// [_See the module-level docs for examples._](.)
+pub use genawaiter_macro::stack_let_send_gen_using as let_send_gen_using;
+
 /// Turns a function into a producer, which can then be used to create a
 /// generator.
 ///
 /// The body of the function should contain one or more [`yield_!`] expressions.
 ///
+/// The attribute takes the yield type, and optionally the resume type, e.g.,
+/// `#[producer_fn(u8, resume = &str)]`. If the function has arguments, calling
+/// it returns a producer which has captured them.
+///
 /// # Examples
 ///
 /// [_See the module-level docs for examples._](.)
@@ -331,6 +420,8 @@ mod macros;
 mod engine;
 mod generator;
 mod iterator;
+#[cfg(feature = "nightly")]
+mod nightly;
 #[cfg(feature = "futures03")]
 mod stream;
 
@@ -341,16 +432,19 @@ mod nightly_tests;
 #[cfg(test)]
 mod tests {
     use crate::{
-        stack::{let_gen_using, Co},
+        stack::{let_gen_using, let_send_gen_using, Co, SendCo},
         testing::DummyFuture,
         GeneratorState,
     };
+    use futures::{executor, future};
     use std::{
         cell::RefCell,
+        panic::{self, AssertUnwindSafe},
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc,
         },
+        thread,
     };
 
     async fn simple_producer(mut co: Co<'_, i32>) -> &'static str {
@@ -413,6 +507,7 @@ mod tests {
 
     #[test]
     #[should_panic(expected = "Co::yield_")]
+    #[allow(clippy::let_underscore_future)]
     fn multiple_yield_helpful_message() {
         async fn wrong(mut co: Co<'_, i32>) {
             let _ = co.yield_(10);
@@ -425,6 +520,7 @@ mod tests {
 
     #[test]
     #[should_panic = "should have been dropped by now"]
+    #[allow(clippy::let_underscore_future)]
     fn escaped_co_helpful_message() {
         async fn shenanigans(co: Co<'_, i32>) -> Co<'_, i32> {
             co
@@ -464,13 +560,113 @@ mod tests {
             // `gen` is only a reference to the generator, and dropping a reference has
             // no effect. The underlying generator is hidden behind macro hygiene and so
             // cannot be dropped early.
-            #[allow(clippy::drop_ref)]
+            #[allow(dropping_references)]
             drop(gen);
-            assert_eq!(flag.load(Ordering::SeqCst), false);
+            assert!(!flag.load(Ordering::SeqCst));
         }
         // After the block above ends, the generator goes out of scope and is dropped,
         // which drops the incomplete future, which drops `_set_on_drop`, which sets the
         // flag.
-        assert_eq!(flag.load(Ordering::SeqCst), true);
+        assert!(flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn restart() {
+        async fn count_to(n: i32, mut co: Co<'_, i32>) {
+            for i in 1..=n {
+                co.yield_(i).await;
+            }
+        }
+
+        let_gen_using!(gen, |co| count_to(1, co));
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+        assert_eq!(gen.resume(), GeneratorState::Complete(()));
+
+        // Restart a generator which has completed.
+        gen.restart(|co| count_to(2, co));
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+
+        // Restart a generator partway through.
+        gen.restart(|co| count_to(3, co));
+        let items: Vec<_> = gen.into_iter().collect();
+        assert_eq!(items, [1, 2, 3]);
+    }
+
+    #[test]
+    fn restart_drops_old_future() {
+        struct SetFlagOnDrop(Arc<AtomicBool>);
+
+        impl Drop for SetFlagOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        async fn produce(flag: Option<Arc<AtomicBool>>, mut co: Co<'_, i32>) {
+            let _set_on_drop = flag.map(SetFlagOnDrop);
+            co.yield_(10).await;
+        }
+
+        let flag = Arc::new(AtomicBool::new(false));
+        let capture_the_flag = flag.clone();
+        let_gen_using!(gen, |co| produce(Some(capture_the_flag), co));
+        assert_eq!(gen.resume(), GeneratorState::Yielded(10));
+        assert!(!flag.load(Ordering::SeqCst));
+        gen.restart(|co| produce(None, co));
+        assert!(flag.load(Ordering::SeqCst));
+        assert_eq!(gen.resume(), GeneratorState::Yielded(10));
+    }
+
+    #[test]
+    fn restart_with_panicking_producer() {
+        async fn count_to(n: i32, mut co: Co<'_, i32>) {
+            for i in 1..=n {
+                co.yield_(i).await;
+            }
+        }
+
+        let_gen_using!(gen, |co| count_to(2, co));
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            gen.restart(|_| panic!("producer failed"));
+        }));
+        assert!(result.is_err());
+        // The old future is left in place, and carries on where it left off.
+        assert_eq!(gen.resume(), GeneratorState::Yielded(2));
+        assert_eq!(gen.resume(), GeneratorState::Complete(()));
+    }
+
+    async fn send_producer(mut co: SendCo<'_, i32>) -> &'static str {
+        co.yield_(10).await;
+        "done"
+    }
+
+    #[test]
+    fn send_between_threads() {
+        let_send_gen_using!(gen, send_producer);
+        assert_eq!(gen.resume(), GeneratorState::Yielded(10));
+        let state = thread::scope(|s| s.spawn(|| gen.resume()).join().unwrap());
+        assert_eq!(state, GeneratorState::Complete("done"));
+    }
+
+    #[test]
+    fn held_across_await() {
+        fn assert_send<T: Send>(x: T) -> T {
+            x
+        }
+
+        let task = assert_send(async {
+            let_send_gen_using!(gen, send_producer);
+            let first = gen.resume();
+            future::ready(()).await;
+            (first, gen.resume())
+        });
+        assert_eq!(
+            executor::block_on(task),
+            (
+                GeneratorState::Yielded(10),
+                GeneratorState::Complete("done")
+            ),
+        );
     }
 }
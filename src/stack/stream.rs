@@ -1,20 +1,26 @@
-use crate::{ops::GeneratorState, stack::Gen};
+use crate::{
+    core,
+    ops::{AsyncCoroutine, GeneratorState},
+    stack::Gen,
+};
 use futures_core::{
     task::{Context, Poll},
     Stream,
 };
 use std::{future::Future, pin::Pin};
 
-impl<'s, Y, F: Future<Output = ()>> Stream for Gen<'s, Y, (), F> {
+impl<Y, F, A> Stream for Gen<'_, Y, (), F, A>
+where
+    F: Future<Output = ()>,
+    A: core::Airlock<Yield = Y, Resume = ()>,
+{
     type Item = Y;
 
     fn poll_next(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let fut = self.async_resume();
-        pin_mut!(fut);
-        match fut.poll(cx) {
+        match self.poll_resume(cx, ()) {
             Poll::Ready(GeneratorState::Yielded(x)) => Poll::Ready(Some(x)),
             Poll::Ready(GeneratorState::Complete(())) => Poll::Ready(None),
             Poll::Pending => Poll::Pending,
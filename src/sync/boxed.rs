@@ -1,15 +1,19 @@
 use crate::sync::{Co, Gen};
 use std::{future::Future, pin::Pin};
 
-/// This is a type alias for generators which can be stored in a `'static`. It's
-/// only really needed to help the compiler's type inference along.
+/// This is a type alias for generators with a boxed future, which makes their
+/// type nameable. It's only really needed to help the compiler's type inference
+/// along.
+///
+/// The future may borrow data for the lifetime `'a`. Use `GenBoxed<'static, _>`
+/// to store a generator in a `static`.
 #[allow(clippy::module_name_repetitions)]
-pub type GenBoxed<Y, R = (), C = ()> =
-    Gen<Y, R, Pin<Box<dyn Future<Output = C> + Send>>>;
+pub type GenBoxed<'a, Y, R = (), C = ()> =
+    Gen<Y, R, Pin<Box<dyn Future<Output = C> + Send + 'a>>>;
 
-impl<Y, R, C> GenBoxed<Y, R, C> {
+impl<'a, Y, R, C> GenBoxed<'a, Y, R, C> {
     /// Creates a new generator with a boxed future, so it can be stored in a
-    /// `static`.
+    /// `static`, or returned from a function.
     ///
     /// This works exactly the same as [`Gen::new`](struct.Gen.html#method.new)
     /// with an immediately boxed future.
@@ -26,12 +30,12 @@ impl<Y, R, C> GenBoxed<Y, R, C> {
     /// #     for n in (1..).step_by(2).take_while(|&n| n < 10) { co.yield_(n).await; }
     /// # }
     /// #
-    /// let _: GenBoxed<i32> = Gen::new_boxed(|co| producer(co));
-    /// let _: GenBoxed<i32> = Gen::new(|co| Box::pin(producer(co)));
+    /// let _: GenBoxed<'static, i32> = Gen::new_boxed(|co| producer(co));
+    /// let _: GenBoxed<'static, i32> = Gen::new(|co| Box::pin(producer(co)));
     /// ```
     pub fn new_boxed<F>(producer: impl FnOnce(Co<Y, R>) -> F) -> Self
     where
-        F: Future<Output = C> + Send + 'static,
+        F: Future<Output = C> + Send + 'a,
     {
         Self::new(|co| Box::pin(producer(co)))
     }
@@ -41,7 +45,7 @@ impl<Y, R, C> GenBoxed<Y, R, C> {
 mod tests {
     use crate::{
         ops::GeneratorState,
-        sync::{Co, Gen},
+        sync::{Co, Gen, GenBoxed},
     };
     use std::sync::{Arc, Mutex};
 
@@ -68,4 +72,23 @@ mod tests {
         assert_eq!(guard.resume(), GeneratorState::Yielded(1));
         assert_eq!(guard.resume(), GeneratorState::Yielded(3));
     }
+
+    #[test]
+    fn borrows_local_data() {
+        fn count_up(limit: &i32) -> GenBoxed<'_, i32> {
+            Gen::new_boxed(move |mut co| {
+                async move {
+                    for n in 0..*limit {
+                        co.yield_(n).await;
+                    }
+                }
+            })
+        }
+
+        let limit = 3;
+        let gen = count_up(&limit);
+        let _: &dyn Send = &gen;
+        let xs: Vec<_> = gen.into_iter().collect();
+        assert_eq!(xs, [0, 1, 2]);
+    }
 }
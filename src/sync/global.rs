@@ -0,0 +1,247 @@
+use crate::{ops::GeneratorState, sync::GenBoxed};
+use std::sync::{Mutex, MutexGuard};
+
+/// A generator which can be stored in a `static`.
+///
+/// It is created by a `const fn`, but it does not create its generator until
+/// the first time it is resumed. After that, it can be resumed through a shared
+/// reference, from any thread. Calls are serialized by an internal lock.
+///
+/// Once the generator completes, or panics, it is dropped, and `next` returns
+/// `None` from then on.
+///
+/// # Examples
+///
+/// ```rust
+/// # use genawaiter::sync::{Co, Gen, GlobalGen};
+/// #
+/// async fn ids(mut co: Co<u64>) {
+///     let mut n = 0;
+///     loop {
+///         n += 1;
+///         co.yield_(n).await;
+///     }
+/// }
+///
+/// static IDS: GlobalGen<u64> = GlobalGen::new(|| Gen::new_boxed(ids));
+///
+/// assert_eq!(IDS.next(), Some(1));
+/// assert_eq!(IDS.next(), Some(2));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct GlobalGen<Y, R = (), C = ()> {
+    start: fn() -> GenBoxed<'static, Y, R, C>,
+    state: Mutex<State<Y, R, C>>,
+}
+
+enum State<Y, R, C> {
+    Unstarted,
+    Started(GenBoxed<'static, Y, R, C>),
+    Finished,
+}
+
+impl<Y, R, C> GlobalGen<Y, R, C> {
+    /// Creates a new global generator.
+    ///
+    /// `start` is called to create the generator the first time it is
+    /// resumed. It is never called more than once.
+    ///
+    /// [_See the type-level docs for examples._](struct.GlobalGen.html)
+    #[must_use]
+    pub const fn new(start: fn() -> GenBoxed<'static, Y, R, C>) -> Self {
+        Self {
+            start,
+            state: Mutex::new(State::Unstarted),
+        }
+    }
+
+    /// Locks the state. If a previous call panicked while holding the lock, the
+    /// generator is left in an unknown state, so it is dropped.
+    fn lock(&self) -> MutexGuard<'_, State<Y, R, C>> {
+        self.state.lock().unwrap_or_else(|poisoned| {
+            let mut state = poisoned.into_inner();
+            *state = State::Finished;
+            self.state.clear_poison();
+            state
+        })
+    }
+
+    /// Resumes the generator, or returns `None` if it has already completed or
+    /// panicked.
+    fn try_resume_with(&self, arg: R) -> Option<GeneratorState<Y, C>> {
+        let mut state = self.lock();
+        if let State::Unstarted = *state {
+            *state = State::Started((self.start)());
+        }
+        let gen = match &mut *state {
+            State::Started(gen) => gen,
+            State::Finished => return None,
+            State::Unstarted => unreachable!(),
+        };
+        let result = gen.resume_with(arg);
+        if let GeneratorState::Complete(_) = result {
+            *state = State::Finished;
+        }
+        Some(result)
+    }
+
+    /// Resumes execution of the generator, creating it first if needed.
+    ///
+    /// `arg` is the resume argument. If the generator was previously paused by
+    /// awaiting a future returned from `co.yield()`, that future will complete,
+    /// and return `arg`.
+    ///
+    /// If the generator yields a value, `Yielded` is returned. Otherwise,
+    /// `Completed` is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator has already completed, or if a previous call
+    /// panicked while resuming it.
+    ///
+    /// [_See the type-level docs for examples._](struct.GlobalGen.html)
+    pub fn resume_with(&self, arg: R) -> GeneratorState<Y, C> {
+        self.try_resume_with(arg)
+            .expect("a global generator was resumed after it completed")
+    }
+}
+
+impl<Y, C> GlobalGen<Y, (), C> {
+    /// Resumes execution of the generator, creating it first if needed.
+    ///
+    /// If the generator yields a value, `Yielded` is returned. Otherwise,
+    /// `Completed` is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator has already completed, or if a previous call
+    /// panicked while resuming it.
+    ///
+    /// [_See the type-level docs for examples._](struct.GlobalGen.html)
+    pub fn resume(&self) -> GeneratorState<Y, C> {
+        self.resume_with(())
+    }
+}
+
+impl<Y> GlobalGen<Y, (), ()> {
+    /// Returns the next value from the generator, or `None` if it has
+    /// completed, or if a previous call panicked while resuming it.
+    ///
+    /// This works like `Iterator::next`, except that it takes `&self`, so it
+    /// can be called on a `static`.
+    ///
+    /// [_See the type-level docs for examples._](struct.GlobalGen.html)
+    pub fn next(&self) -> Option<Y> {
+        match self.try_resume_with(())? {
+            GeneratorState::Yielded(x) => Some(x),
+            GeneratorState::Complete(()) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::GeneratorState,
+        sync::{Co, Gen, GlobalGen},
+    };
+    use std::{
+        panic,
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    #[test]
+    fn lazy_start() {
+        static STARTS: AtomicUsize = AtomicUsize::new(0);
+        static GEN: GlobalGen<i32> = GlobalGen::new(|| {
+            STARTS.fetch_add(1, Ordering::SeqCst);
+            Gen::new_boxed(|mut co| {
+                async move {
+                    co.yield_(10).await;
+                    co.yield_(20).await;
+                }
+            })
+        });
+
+        assert_eq!(STARTS.load(Ordering::SeqCst), 0);
+        assert_eq!(GEN.next(), Some(10));
+        assert_eq!(GEN.next(), Some(20));
+        assert_eq!(GEN.next(), None);
+        assert_eq!(GEN.next(), None);
+        assert_eq!(STARTS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    #[should_panic = "resumed after it completed"]
+    fn resume_after_complete() {
+        static GEN: GlobalGen<i32> = GlobalGen::new(|| Gen::new_boxed(|_| async {}));
+
+        assert_eq!(GEN.resume(), GeneratorState::Complete(()));
+        GEN.resume();
+    }
+
+    #[test]
+    fn producer_panics() {
+        static GEN: GlobalGen<i32> = GlobalGen::new(|| {
+            Gen::new_boxed(|mut co| {
+                async move {
+                    co.yield_(10).await;
+                    panic!("producer failed");
+                }
+            })
+        });
+
+        assert_eq!(GEN.next(), Some(10));
+        assert!(panic::catch_unwind(|| GEN.next()).is_err());
+        assert_eq!(GEN.next(), None);
+        assert_eq!(GEN.next(), None);
+    }
+
+    #[test]
+    fn resume_with() {
+        async fn running_total(mut co: Co<i32, i32>) -> &'static str {
+            let mut total = 0;
+            loop {
+                total += co.yield_(total).await;
+                if total > 5 {
+                    return "done";
+                }
+            }
+        }
+
+        static GEN: GlobalGen<i32, i32, &'static str> =
+            GlobalGen::new(|| Gen::new_boxed(running_total));
+
+        assert_eq!(GEN.resume_with(-1), GeneratorState::Yielded(0));
+        assert_eq!(GEN.resume_with(2), GeneratorState::Yielded(2));
+        assert_eq!(GEN.resume_with(4), GeneratorState::Complete("done"));
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        static IDS: GlobalGen<usize> = GlobalGen::new(|| {
+            Gen::new_boxed(|mut co| {
+                async move {
+                    for n in 0.. {
+                        co.yield_(n).await;
+                    }
+                }
+            })
+        });
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                thread::spawn(|| {
+                    (0..100).map(|_| IDS.next().unwrap()).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut ids: Vec<_> = threads
+            .into_iter()
+            .flat_map(|t| t.join().unwrap())
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, (0..400).collect::<Vec<_>>());
+    }
+}
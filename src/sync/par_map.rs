@@ -0,0 +1,430 @@
+use crate::sync::Gen;
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, SyncSender},
+        Arc,
+        Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+impl<Y, F> Gen<Y, (), F>
+where
+    Y: Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    /// Moves the generator to a worker thread, and maps each value it yields on
+    /// a pool of `threads` more worker threads. Returns an iterator over the
+    /// results, in the order the values were yielded.
+    ///
+    /// Results which finish early are held until every earlier result has been
+    /// returned. If the order does not matter, [`par_map_unordered`] avoids
+    /// this wait.
+    ///
+    /// At most `threads` values are taken from the generator ahead of the
+    /// results returned so far. So a value which takes a long time to map
+    /// stalls the pool, rather than letting the held results pile up.
+    ///
+    /// If the generator or `f` panics, the panic is propagated to the consumer.
+    /// If the iterator is dropped early, the workers stop after the values they
+    /// are currently working on, the generator is dropped, and every worker is
+    /// joined.
+    ///
+    /// [`par_map_unordered`]: Gen::par_map_unordered
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use genawaiter::sync::{Co, Gen};
+    ///
+    /// async fn numbers(mut co: Co<u64>) {
+    ///     for n in 1..=5 {
+    ///         co.yield_(n).await;
+    ///     }
+    /// }
+    ///
+    /// let squares: Vec<_> = Gen::new(numbers).par_map(4, |n| n * n).collect();
+    /// assert_eq!(squares, [1, 4, 9, 16, 25]);
+    /// ```
+    #[must_use]
+    pub fn par_map<T, M>(self, threads: usize, f: M) -> ParMap<T>
+    where
+        T: Send + 'static,
+        M: Fn(Y) -> T + Send + Sync + 'static,
+    {
+        ParMap {
+            pool: Pool::new(self, threads, f),
+            pending: BTreeMap::new(),
+            next: 0,
+        }
+    }
+
+    /// Moves the generator to a worker thread, and maps each value it yields on
+    /// a pool of `threads` more worker threads. Returns an iterator over the
+    /// results, in the order they finish.
+    ///
+    /// This works like [`par_map`](Gen::par_map), except for the order of the
+    /// results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use genawaiter::sync::{Co, Gen};
+    ///
+    /// async fn numbers(mut co: Co<u64>) {
+    ///     for n in 1..=5 {
+    ///         co.yield_(n).await;
+    ///     }
+    /// }
+    ///
+    /// let mut squares: Vec<_> =
+    ///     Gen::new(numbers).par_map_unordered(4, |n| n * n).collect();
+    /// squares.sort();
+    /// assert_eq!(squares, [1, 4, 9, 16, 25]);
+    /// ```
+    #[must_use]
+    pub fn par_map_unordered<T, M>(self, threads: usize, f: M) -> ParMapUnordered<T>
+    where
+        T: Send + 'static,
+        M: Fn(Y) -> T + Send + Sync + 'static,
+    {
+        ParMapUnordered {
+            pool: Pool::new(self, threads, f),
+        }
+    }
+}
+
+/// An iterator over the results of mapping a generator's values on a thread
+/// pool, in the order the values were yielded.
+///
+/// This is created by [`Gen::par_map`]. See its documentation for more.
+#[allow(clippy::module_name_repetitions)]
+pub struct ParMap<T> {
+    pool: Pool<T>,
+    pending: BTreeMap<usize, T>,
+    next: usize,
+}
+
+impl<T> Iterator for ParMap<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.pending.remove(&self.next) {
+                self.next += 1;
+                self.pool.release();
+                return Some(result);
+            }
+            let (index, result) = self.pool.recv()?;
+            self.pending.insert(index, result);
+        }
+    }
+}
+
+/// An iterator over the results of mapping a generator's values on a thread
+/// pool, in the order they finish.
+///
+/// This is created by [`Gen::par_map_unordered`]. See its documentation for
+/// more.
+#[allow(clippy::module_name_repetitions)]
+pub struct ParMapUnordered<T> {
+    pool: Pool<T>,
+}
+
+impl<T> Iterator for ParMapUnordered<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, result) = self.pool.recv()?;
+        self.pool.release();
+        Some(result)
+    }
+}
+
+type Job<Y> = (usize, Y);
+type Outcome<T> = (usize, thread::Result<T>);
+
+/// The threads behind `ParMap` and `ParMapUnordered`. One thread resumes the
+/// generator and hands out numbered jobs, and the rest run the mapping function
+/// and send back numbered results.
+///
+/// Each job takes one of `threads` slots, which is given back once its result
+/// has been returned to the consumer. This bounds the results which can be
+/// waiting, however slow any one job is.
+struct Pool<T> {
+    results: Option<Receiver<Outcome<T>>>,
+    slots: Option<SyncSender<()>>,
+    stop: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl<T: Send + 'static> Pool<T> {
+    fn new<Y, F, M>(generator: Gen<Y, (), F>, threads: usize, f: M) -> Self
+    where
+        Y: Send + 'static,
+        F: Future<Output = ()> + Send + 'static,
+        M: Fn(Y) -> T + Send + Sync + 'static,
+    {
+        assert!(threads > 0, "`par_map` needs at least one thread");
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (job_sender, jobs) = mpsc::sync_channel::<Job<Y>>(threads);
+        let (result_sender, results) = mpsc::sync_channel(threads);
+        let (slot_sender, slots) = mpsc::sync_channel(threads);
+        for _ in 0..threads {
+            slot_sender.send(()).unwrap();
+        }
+        let jobs = Arc::new(Mutex::new(jobs));
+        let f = Arc::new(f);
+
+        let mut handles = Vec::with_capacity(threads + 1);
+        handles.push({
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let mut jobs = generator.into_iter().enumerate();
+                // Wait for a free slot before resuming the generator.
+                while slots.recv().is_ok() {
+                    let Some(job) = jobs.next() else { break };
+                    if stop.load(Ordering::SeqCst) || job_sender.send(job).is_err() {
+                        break;
+                    }
+                }
+            })
+        });
+        for _ in 0..threads {
+            let jobs = jobs.clone();
+            let results = result_sender.clone();
+            let stop = stop.clone();
+            let f = f.clone();
+            handles.push(thread::spawn(move || {
+                loop {
+                    // Hold the lock only while waiting, not while working.
+                    let job = jobs.lock().unwrap().recv();
+                    let Ok((index, value)) = job else { break };
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| f(value)));
+                    if results.send((index, result)).is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+
+        Self {
+            results: Some(results),
+            slots: Some(slot_sender),
+            stop,
+            threads: handles,
+        }
+    }
+}
+
+impl<T> Pool<T> {
+    /// Receives the next result from any worker. Returns `None` once the
+    /// generator has completed and every result has been received.
+    fn recv(&mut self) -> Option<(usize, T)> {
+        match self.results.as_ref()?.recv() {
+            Ok((index, Ok(result))) => Some((index, result)),
+            Ok((_, Err(payload))) => {
+                let _ = self.shut_down();
+                panic::resume_unwind(payload)
+            }
+            Err(_) => {
+                // Every worker has exited, so the generator has either completed
+                // or panicked.
+                if let Err(payload) = self.shut_down() {
+                    panic::resume_unwind(payload);
+                }
+                None
+            }
+        }
+    }
+
+    /// Gives back the slot of a job whose result has been returned, so the
+    /// generator can be resumed for another.
+    fn release(&self) {
+        if let Some(slots) = &self.slots {
+            // There is always room, since only a taken slot is given back.
+            let _ = slots.try_send(());
+        }
+    }
+
+    /// Tells every thread to stop, and waits for them to exit. Returns whatever
+    /// the generator panicked with, if it panicked.
+    fn shut_down(&mut self) -> Result<(), Box<dyn Any + Send>> {
+        self.stop.store(true, Ordering::SeqCst);
+        drop(self.results.take());
+        drop(self.slots.take());
+        let mut outcome = Ok(());
+        for handle in self.threads.drain(..) {
+            if let Err(payload) = handle.join() {
+                outcome = outcome.and(Err(payload));
+            }
+        }
+        outcome
+    }
+}
+
+impl<T> Drop for Pool<T> {
+    fn drop(&mut self) {
+        // A panic is only propagated through `recv`, since panicking here could
+        // abort the process.
+        let _ = self.shut_down();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sync::{Co, Gen};
+    use std::{
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    async fn count_to(n: u64, mut co: Co<u64>) {
+        for i in 1..=n {
+            co.yield_(i).await;
+        }
+    }
+
+    #[test]
+    fn ordered() {
+        let gen = Gen::new(|co| count_to(50, co));
+        let results: Vec<_> = gen
+            .par_map(4, |n| {
+                // Make early values finish last.
+                thread::sleep(Duration::from_millis(50 - n));
+                n * 2
+            })
+            .collect();
+        assert_eq!(results, (1..=50).map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn slow_value_bounds_buffer() {
+        let yielded = Arc::new(AtomicUsize::new(0));
+        let yielded_ = yielded.clone();
+        let gen = Gen::new(|mut co| {
+            async move {
+                for n in 0.. {
+                    yielded_.fetch_add(1, Ordering::SeqCst);
+                    co.yield_(n).await;
+                }
+            }
+        });
+
+        let seen = Arc::new(AtomicUsize::new(0));
+        let (yielded_, seen_) = (yielded.clone(), seen.clone());
+        let results: Vec<_> = gen
+            .par_map(4, move |n: u64| {
+                if n == 0 {
+                    // Give the later values time to finish.
+                    thread::sleep(Duration::from_millis(100));
+                    seen_.store(yielded_.load(Ordering::SeqCst), Ordering::SeqCst);
+                }
+                n
+            })
+            .take(20)
+            .collect();
+        assert_eq!(results, (0..20).collect::<Vec<_>>());
+        assert!(seen.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[test]
+    fn unordered() {
+        let gen = Gen::new(|co| count_to(50, co));
+        let mut results: Vec<_> = gen.par_map_unordered(4, |n| n * 2).collect();
+        results.sort_unstable();
+        assert_eq!(results, (1..=50).map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn uses_every_thread() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let gen = Gen::new(|co| count_to(16, co));
+        let (running_, peak_) = (running.clone(), peak.clone());
+        let count = gen
+            .par_map_unordered(4, move |_| {
+                let now = running_.fetch_add(1, Ordering::SeqCst) + 1;
+                peak_.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(20));
+                running_.fetch_sub(1, Ordering::SeqCst);
+            })
+            .count();
+        assert_eq!(count, 16);
+        assert!(peak.load(Ordering::SeqCst) > 1);
+    }
+
+    #[test]
+    fn early_drop_stops_generator() {
+        struct SetOnDrop(Arc<AtomicBool>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+        let gen = Gen::new(|mut co| {
+            async move {
+                let _guard = guard;
+                let mut n = 0;
+                loop {
+                    n += 1;
+                    co.yield_(n).await;
+                }
+            }
+        });
+
+        let mut results = gen.par_map(2, |n: u64| n + 1);
+        assert_eq!(results.next(), Some(2));
+        drop(results);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic = "bad value"]
+    fn propagates_worker_panic() {
+        let gen = Gen::new(|co| count_to(10, co));
+        let _: Vec<_> = gen
+            .par_map(2, |n| {
+                assert!(n != 5, "bad value");
+                n
+            })
+            .collect();
+    }
+
+    #[test]
+    #[should_panic = "producer failed"]
+    fn propagates_generator_panic() {
+        let gen = Gen::new(|mut co| {
+            async move {
+                co.yield_(1).await;
+                panic!("producer failed");
+            }
+        });
+        let _: Vec<u64> = gen.par_map(2, |n| n).collect();
+    }
+}
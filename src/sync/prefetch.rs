@@ -0,0 +1,180 @@
+use crate::sync::Gen;
+use std::{
+    future::Future,
+    panic,
+    sync::mpsc::{self, Receiver},
+    thread::{self, JoinHandle},
+};
+
+impl<Y, F> Gen<Y, (), F>
+where
+    Y: Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    /// Moves the generator to a worker thread, and returns an iterator over the
+    /// values it yields.
+    ///
+    /// The worker resumes the generator ahead of the consumer, and buffers up to
+    /// `capacity` values, so producing values overlaps with consuming them. With
+    /// a `capacity` of `0`, the worker hands over each value directly, and so
+    /// runs at most one value ahead.
+    ///
+    /// If the generator panics, the panic is propagated to the consumer once it
+    /// has received every value yielded before the panic. If the iterator is
+    /// dropped early, the worker stops at its next yield, drops the generator,
+    /// and is joined.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use genawaiter::sync::{Co, Gen};
+    ///
+    /// async fn decode(mut co: Co<u32>) {
+    ///     for n in 0..100 {
+    ///         // Pretend this is expensive.
+    ///         co.yield_(n * n).await;
+    ///     }
+    /// }
+    ///
+    /// let squares = Gen::new(decode).into_prefetch_iter(16);
+    /// let total: u32 = squares.sum();
+    /// assert_eq!(total, 328_350);
+    /// ```
+    #[must_use]
+    pub fn into_prefetch_iter(self, capacity: usize) -> PrefetchIter<Y> {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let worker = thread::spawn(move || {
+            for value in self {
+                if sender.send(value).is_err() {
+                    // The consumer hung up.
+                    break;
+                }
+            }
+        });
+        PrefetchIter {
+            receiver: Some(receiver),
+            worker: Some(worker),
+        }
+    }
+}
+
+/// An iterator over the values yielded by a generator running on a worker
+/// thread.
+///
+/// This is created by [`Gen::into_prefetch_iter`]. See its documentation for
+/// more.
+#[allow(clippy::module_name_repetitions)]
+pub struct PrefetchIter<Y> {
+    receiver: Option<Receiver<Y>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<Y> PrefetchIter<Y> {
+    /// Hangs up on the worker, and waits for it to exit. Returns whatever the
+    /// worker panicked with, if it panicked.
+    fn shut_down(&mut self) -> thread::Result<()> {
+        drop(self.receiver.take());
+        match self.worker.take() {
+            Some(worker) => worker.join(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<Y> Iterator for PrefetchIter<Y> {
+    type Item = Y;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.receiver.as_ref()?.recv().ok();
+        if value.is_none() {
+            // The worker dropped its sender, so it has either completed or
+            // panicked.
+            if let Err(payload) = self.shut_down() {
+                panic::resume_unwind(payload);
+            }
+        }
+        value
+    }
+}
+
+impl<Y> Drop for PrefetchIter<Y> {
+    fn drop(&mut self) {
+        // A panic in the worker is only propagated through `next`, since
+        // panicking here could abort the process.
+        let _ = self.shut_down();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sync::{Co, Gen};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn yields_in_order() {
+        async fn produce(mut co: Co<i32>) {
+            for n in 0..10 {
+                co.yield_(n).await;
+            }
+        }
+
+        let items: Vec<_> = Gen::new(produce).into_prefetch_iter(3).collect();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn rendezvous() {
+        async fn produce(mut co: Co<i32>) {
+            co.yield_(10).await;
+            co.yield_(20).await;
+        }
+
+        let items: Vec<_> = Gen::new(produce).into_prefetch_iter(0).collect();
+        assert_eq!(items, [10, 20]);
+    }
+
+    #[test]
+    fn early_drop_stops_worker() {
+        struct SetOnDrop(Arc<AtomicBool>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+        let gen = Gen::new(|mut co| {
+            async move {
+                let _guard = guard;
+                loop {
+                    co.yield_(1).await;
+                }
+            }
+        });
+
+        let mut iter = gen.into_prefetch_iter(1);
+        assert_eq!(iter.next(), Some(1));
+        drop(iter);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic = "producer failed"]
+    fn propagates_panic() {
+        let gen = Gen::new(|mut co| {
+            async move {
+                co.yield_(1).await;
+                panic!("producer failed");
+            }
+        });
+
+        let mut iter = gen.into_prefetch_iter(1);
+        assert_eq!(iter.next(), Some(1));
+        iter.next();
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    panic,
+    sync::mpsc::{Receiver, Sender},
+};
+
+/// A message sent from the producer's thread to the generator. After `Done`, the
+/// completion value (or panic) is collected by joining the thread.
+pub enum Message<Y> {
+    Yield(Y),
+    Done,
+}
+
+/// The panic payload used to unwind the producer's thread when its generator is
+/// dropped.
+pub struct Cancelled;
+
+/// This object lets you yield values from the generator by calling the `yield_`
+/// method.
+///
+/// It is the counterpart of `Co` in the other engines, except that yielding is
+/// an ordinary blocking call instead of a future.
+///
+/// [_See the module-level docs for examples._](.)
+pub struct Yielder<Y, R = ()> {
+    messages: Sender<Message<Y>>,
+    resumes: Receiver<R>,
+}
+
+impl<Y, R> Yielder<Y, R> {
+    pub(crate) fn new(messages: Sender<Message<Y>>, resumes: Receiver<R>) -> Self {
+        Self { messages, resumes }
+    }
+
+    /// Waits until the generator is first resumed. Returns `false` if it was
+    /// dropped instead.
+    pub(crate) fn wait_for_start(&self) -> bool {
+        self.resumes.recv().is_ok()
+    }
+
+    /// Yields a value from the generator, and blocks until the generator is
+    /// resumed again. Returns the resume argument.
+    ///
+    /// If the generator is dropped instead of being resumed, this unwinds the
+    /// producer's thread, so that everything on its stack is dropped too.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn yield_(&mut self, value: Y) -> R {
+        let sent = self.messages.send(Message::Yield(value));
+        match sent.ok().and_then(|()| self.resumes.recv().ok()) {
+            Some(arg) => arg,
+            // `resume_unwind` skips the panic hook, so nothing is printed.
+            None => panic::resume_unwind(Box::new(Cancelled)),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    ops::{BoxCoroutine, Coroutine, GeneratorState},
+    thread::engine::{Cancelled, Message, Yielder},
+};
+use std::{
+    mem,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::mpsc::{self, Receiver, Sender},
+    thread::{self, JoinHandle},
+};
+
+/// This is a generator which runs its producer on a dedicated thread.
+///
+/// [_See the module-level docs for examples._](.)
+pub struct Gen<Y, R = (), C = ()> {
+    resumes: Sender<R>,
+    messages: Receiver<Message<Y>>,
+    thread: Option<JoinHandle<C>>,
+}
+
+impl<Y, R, C> Gen<Y, R, C>
+where
+    Y: Send + 'static,
+    R: Send + 'static,
+    C: Send + 'static,
+{
+    /// Creates a new generator from a function.
+    ///
+    /// The function accepts a [`Yielder`] object, and is run on a new thread.
+    /// It does not start running until the generator is first resumed. Every
+    /// time it calls `yielder.yield_()`, the thread blocks until the generator
+    /// is resumed again. Whatever the function returns becomes the completion
+    /// value.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn new(producer: impl FnOnce(Yielder<Y, R>) -> C + Send + 'static) -> Self {
+        let (resume_sender, resumes) = mpsc::channel();
+        let (message_sender, messages) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            let yielder = Yielder::new(message_sender.clone(), resumes);
+            if !yielder.wait_for_start() {
+                panic::resume_unwind(Box::new(Cancelled));
+            }
+            let result = panic::catch_unwind(AssertUnwindSafe(|| producer(yielder)));
+            let _ = message_sender.send(Message::Done);
+            match result {
+                Ok(value) => value,
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        });
+        Self {
+            resumes: resume_sender,
+            messages,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<Y, R, C> Gen<Y, R, C> {
+    /// Resumes execution of the generator.
+    ///
+    /// `arg` is the resume argument. If the producer was previously paused in
+    /// `yielder.yield_()`, that call will return `arg`.
+    ///
+    /// If the generator yields a value, `Yielded` is returned. Otherwise,
+    /// `Completed` is returned. If the producer panics, the panic is propagated
+    /// to the caller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator has already completed.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume_with(&mut self, arg: R) -> GeneratorState<Y, C> {
+        let thread = self
+            .thread
+            .take()
+            .expect("a thread generator was resumed after it completed");
+        // If the producer's thread is gone, the message below says why.
+        let _ = self.resumes.send(arg);
+        match self.messages.recv() {
+            Ok(Message::Yield(value)) => {
+                self.thread = Some(thread);
+                GeneratorState::Yielded(value)
+            }
+            Ok(Message::Done) | Err(_) => {
+                match thread.join() {
+                    Ok(value) => GeneratorState::Complete(value),
+                    Err(payload) => panic::resume_unwind(payload),
+                }
+            }
+        }
+    }
+
+    /// Boxes this generator, so it can be stored alongside generators of other
+    /// types, as a [`BoxCoroutine`].
+    ///
+    /// [_See the `DynCoroutine` docs for examples._](../ops/trait.DynCoroutine.html)
+    #[must_use]
+    pub fn boxed<'a>(self) -> BoxCoroutine<'a, Y, R, C>
+    where
+        Self: 'a,
+    {
+        Box::pin(self)
+    }
+}
+
+impl<Y, C> Gen<Y, (), C> {
+    /// Resumes execution of the generator.
+    ///
+    /// If the generator yields a value, `Yielded` is returned. Otherwise,
+    /// `Completed` is returned.
+    ///
+    /// [_See the module-level docs for examples._](.)
+    pub fn resume(&mut self) -> GeneratorState<Y, C> {
+        self.resume_with(())
+    }
+}
+
+impl<Y, R, C> Drop for Gen<Y, R, C> {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            // Hang up, so the producer unwinds out of `yield_` (or never starts),
+            // and wait for its stack to be dropped.
+            let (resumes, _) = mpsc::channel();
+            drop(mem::replace(&mut self.resumes, resumes));
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<Y, R, C> Coroutine for Gen<Y, R, C> {
+    type Yield = Y;
+    type Resume = R;
+    type Return = C;
+
+    fn resume_with(
+        mut self: Pin<&mut Self>,
+        arg: R,
+    ) -> GeneratorState<Self::Yield, Self::Return> {
+        Self::resume_with(&mut *self, arg)
+    }
+}
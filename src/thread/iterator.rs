@@ -0,0 +1,52 @@
+use crate::{ops::GeneratorState, thread::Gen};
+
+impl<Y> IntoIterator for Gen<Y, (), ()> {
+    type Item = Y;
+    type IntoIter = IntoIter<Y>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { generator: self }
+    }
+}
+
+pub struct IntoIter<Y> {
+    generator: Gen<Y, (), ()>,
+}
+
+impl<Y> Iterator for IntoIter<Y> {
+    type Item = Y;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.generator.resume() {
+            GeneratorState::Yielded(x) => Some(x),
+            GeneratorState::Complete(()) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::thread::{Gen, Yielder};
+    use std::iter::IntoIterator;
+
+    fn produce(mut y: Yielder<i32>) {
+        y.yield_(10);
+        y.yield_(20);
+    }
+
+    #[test]
+    fn into_iter() {
+        let gen = Gen::new(produce);
+        let items: Vec<_> = gen.into_iter().collect();
+        assert_eq!(items, [10, 20]);
+    }
+
+    #[test]
+    fn for_loop() {
+        let mut sum = 0;
+        for x in Gen::new(produce) {
+            sum += x;
+        }
+        assert_eq!(sum, 30);
+    }
+}
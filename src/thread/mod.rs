@@ -0,0 +1,229 @@
+/*!
+This module implements a generator which runs its producer on a dedicated thread.
+
+Unlike the other engines, the producer is an ordinary function rather than an `async`
+one. It receives a [`Yielder`], and `yield_` is a plain blocking call. This means
+the producer can recurse without boxing, and can yield from inside callbacks – for
+example, to turn a visitor API into an iterator.
+
+The trade-off is a thread per generator, and a round trip between threads for every
+value.
+
+```rust
+use genawaiter::thread::{Gen, Yielder};
+
+fn my_producer(mut yielder: Yielder<u8>) {
+    yielder.yield_(10);
+}
+let mut my_generator = Gen::new(my_producer);
+# my_generator.resume();
+```
+
+The producer does not start running until the generator is first resumed. If the
+generator is dropped before the producer completes, `yield_` unwinds the producer's
+thread, so every value on its stack is dropped, and the drop waits for that to
+finish. (This means the producer should not catch panics around `yield_`.)
+
+# Examples
+
+## Using `Iterator`
+
+Generators implement `IntoIterator`, so you can use them in a for loop:
+
+```rust
+use genawaiter::thread::{Gen, Yielder};
+
+let odds_under_ten = Gen::new(|mut y: Yielder<i32>| {
+    for n in (1..).step_by(2).take_while(|&n| n < 10) {
+        y.yield_(n);
+    }
+});
+
+# let mut test = Vec::new();
+for num in odds_under_ten {
+    println!("{}", num);
+    # test.push(num);
+}
+# assert_eq!(test, [1, 3, 5, 7, 9]);
+```
+
+## Recursion
+
+The producer's stack is a real stack, so recursive helpers can take the `Yielder` by
+reference.
+
+```rust
+use genawaiter::thread::{Gen, Yielder};
+
+enum Tree {
+    Leaf(i32),
+    Node(Vec<Tree>),
+}
+
+fn walk(tree: &Tree, y: &mut Yielder<i32>) {
+    match tree {
+        Tree::Leaf(n) => {
+            y.yield_(*n);
+        }
+        Tree::Node(children) => {
+            for child in children {
+                walk(child, y);
+            }
+        }
+    }
+}
+
+let tree = Tree::Node(vec![
+    Tree::Leaf(1),
+    Tree::Node(vec![Tree::Leaf(2), Tree::Leaf(3)]),
+]);
+let leaves: Vec<_> = Gen::new(move |mut y| walk(&tree, &mut y)).into_iter().collect();
+assert_eq!(leaves, [1, 2, 3]);
+```
+
+## Resume arguments and completion values
+
+```rust
+use genawaiter::{
+    thread::{Gen, Yielder},
+    GeneratorState,
+};
+
+let mut gen = Gen::new(|mut y: Yielder<i32, i32>| {
+    let mut total = 0;
+    loop {
+        let n = y.yield_(total);
+        if n == 0 {
+            return total;
+        }
+        total += n;
+    }
+});
+assert_eq!(gen.resume_with(-1), GeneratorState::Yielded(0));
+assert_eq!(gen.resume_with(5), GeneratorState::Yielded(5));
+assert_eq!(gen.resume_with(6), GeneratorState::Yielded(11));
+assert_eq!(gen.resume_with(0), GeneratorState::Complete(11));
+```
+*/
+
+pub use crate::thread::{engine::Yielder, generator::Gen};
+
+mod engine;
+mod generator;
+mod iterator;
+#[cfg(feature = "nightly")]
+mod nightly;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        thread::{Gen, Yielder},
+        GeneratorState,
+    };
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Mutex,
+    };
+
+    fn simple_producer(mut y: Yielder<i32>) -> &'static str {
+        y.yield_(10);
+        "done"
+    }
+
+    #[test]
+    fn function() {
+        let mut gen = Gen::new(simple_producer);
+        assert_eq!(gen.resume(), GeneratorState::Yielded(10));
+        assert_eq!(gen.resume(), GeneratorState::Complete("done"));
+    }
+
+    #[test]
+    fn resume_args() {
+        let resumes = Arc::new(Mutex::new(Vec::new()));
+        let resumes_ = resumes.clone();
+        let mut gen = Gen::new(move |mut y: Yielder<i32, &'static str>| {
+            let resume_arg = y.yield_(10);
+            resumes_.lock().unwrap().push(resume_arg);
+            let resume_arg = y.yield_(20);
+            resumes_.lock().unwrap().push(resume_arg);
+        });
+        assert_eq!(*resumes.lock().unwrap(), &[] as &[&str]);
+
+        assert_eq!(gen.resume_with("ignored"), GeneratorState::Yielded(10));
+        assert_eq!(*resumes.lock().unwrap(), &[] as &[&str]);
+
+        assert_eq!(gen.resume_with("abc"), GeneratorState::Yielded(20));
+        assert_eq!(*resumes.lock().unwrap(), &["abc"]);
+
+        assert_eq!(gen.resume_with("def"), GeneratorState::Complete(()));
+        assert_eq!(*resumes.lock().unwrap(), &["abc", "def"]);
+    }
+
+    #[test]
+    fn lazy_start() {
+        let started = Arc::new(AtomicBool::new(false));
+        let started_ = started.clone();
+        let mut gen = Gen::new(move |mut y: Yielder<()>| {
+            started_.store(true, Ordering::SeqCst);
+            y.yield_(());
+        });
+        assert!(!started.load(Ordering::SeqCst));
+        gen.resume();
+        assert!(started.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn drop_unwinds_producer() {
+        struct SetOnDrop(Arc<AtomicBool>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+        let mut gen = Gen::new(move |mut y| {
+            let _guard = guard;
+            loop {
+                y.yield_(1);
+            }
+        });
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(gen);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn drop_before_start() {
+        let started = Arc::new(AtomicBool::new(false));
+        let started_ = started.clone();
+        let gen = Gen::new(move |_: Yielder<()>| {
+            started_.store(true, Ordering::SeqCst);
+        });
+        drop(gen);
+        assert!(!started.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic = "producer failed"]
+    fn propagates_panic() {
+        let mut gen = Gen::new(|mut y: Yielder<i32>| {
+            y.yield_(1);
+            panic!("producer failed");
+        });
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+        gen.resume();
+    }
+
+    #[test]
+    #[should_panic = "resumed after it completed"]
+    fn resume_after_complete() {
+        let mut gen = Gen::new(|_: Yielder<i32>| {});
+        assert_eq!(gen.resume(), GeneratorState::Complete(()));
+        gen.resume();
+    }
+}
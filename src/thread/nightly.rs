@@ -0,0 +1,38 @@
+use crate::{ops::Coroutine, thread::Gen};
+use std::{
+    ops::{self, CoroutineState},
+    pin::Pin,
+};
+
+impl<Y, R, C> ops::Coroutine<R> for Gen<Y, R, C> {
+    type Yield = Y;
+    type Return = C;
+
+    fn resume(self: Pin<&mut Self>, arg: R) -> CoroutineState<Y, C> {
+        Coroutine::resume_with(self, arg).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::thread::{Gen, Yielder};
+    use std::{
+        ops::{Coroutine, CoroutineState},
+        pin::Pin,
+    };
+
+    #[test]
+    fn std_coroutine() {
+        fn produce(mut y: Yielder<i32, i32>) -> &'static str {
+            let n = y.yield_(10);
+            y.yield_(n + 1);
+            "done"
+        }
+
+        let mut gen = Gen::new(produce);
+        let mut gen = Pin::new(&mut gen);
+        assert_eq!(gen.as_mut().resume(0), CoroutineState::Yielded(10));
+        assert_eq!(gen.as_mut().resume(5), CoroutineState::Yielded(6));
+        assert_eq!(gen.as_mut().resume(0), CoroutineState::Complete("done"));
+    }
+}
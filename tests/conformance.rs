@@ -0,0 +1,172 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![warn(clippy::pedantic)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+
+mod rc_conformance {
+    genawaiter::engine_conformance_tests!(genawaiter::rc::Gen::new);
+}
+
+mod sync_conformance {
+    genawaiter::engine_conformance_tests!(genawaiter::sync::Gen::new);
+}
+
+// The `inline` engine is not covered. `inline::Gen::new` needs a producer which
+// accepts a `Co` with any lifetime, but the producers in the suite are generic
+// over the airlock type, so each one accepts a `Co` with only a single lifetime.
+// Nor is the `thread` engine, whose producer is not `async`, and has no `Co`.
+
+/// The `stack` engine, through a wrapper which owns its shelf.
+mod stack {
+    use genawaiter::{
+        stack::{Co, Gen, Shelf},
+        Coroutine,
+        GeneratorState,
+    };
+    use std::{future::Future, mem::ManuallyDrop, pin::Pin};
+
+    /// A stack generator which owns its shelf, so the suite can box it, like
+    /// the other engines' generators.
+    pub struct OwnedGen<Y: 'static, R: 'static, F: Future + 'static> {
+        gen: ManuallyDrop<Gen<'static, Y, R, F>>,
+        shelf: *mut Shelf<Y, R, F>,
+    }
+
+    impl<Y, R, F: Future> OwnedGen<Y, R, F> {
+        pub fn new(producer: impl FnOnce(Co<'static, Y, R>) -> F) -> Self {
+            let shelf = Box::into_raw(Box::new(Shelf::new()));
+            // Safety: The shelf is not freed until the generator is dropped (see
+            // below). The producers in the suite do not leak their `Co`.
+            let gen = unsafe { Gen::new(&mut *shelf, producer) };
+            Self {
+                gen: ManuallyDrop::new(gen),
+                shelf,
+            }
+        }
+    }
+
+    impl<Y, R, F: Future> Drop for OwnedGen<Y, R, F> {
+        fn drop(&mut self) {
+            // Safety: The generator is dropped before the shelf it borrows, and
+            // neither is used again.
+            unsafe {
+                ManuallyDrop::drop(&mut self.gen);
+                drop(Box::from_raw(self.shelf));
+            }
+        }
+    }
+
+    impl<Y, R, F: Future> Coroutine for OwnedGen<Y, R, F> {
+        type Yield = Y;
+        type Resume = R;
+        type Return = F::Output;
+
+        fn resume_with(
+            mut self: Pin<&mut Self>,
+            arg: R,
+        ) -> GeneratorState<Self::Yield, Self::Return> {
+            self.gen.resume_with(arg)
+        }
+    }
+
+    mod conformance {
+        genawaiter::engine_conformance_tests!(super::OwnedGen::new);
+    }
+}
+
+/// A custom engine, which counts how many values pass through its airlock.
+mod counting {
+    use genawaiter::{
+        engine::{advance, Airlock, Next},
+        Co,
+        Coroutine,
+        GeneratorState,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        future::Future,
+        mem,
+        pin::Pin,
+        rc::Rc,
+    };
+
+    pub struct CountingAirlock<Y, R> {
+        next: Rc<RefCell<Next<Y, R>>>,
+        yields: Rc<Cell<usize>>,
+    }
+
+    impl<Y, R> Clone for CountingAirlock<Y, R> {
+        fn clone(&self) -> Self {
+            Self {
+                next: self.next.clone(),
+                yields: self.yields.clone(),
+            }
+        }
+    }
+
+    impl<Y, R> Airlock for CountingAirlock<Y, R> {
+        type Yield = Y;
+        type Resume = R;
+
+        fn peek(&self) -> Next<(), ()> {
+            self.next.borrow().without_values()
+        }
+
+        fn replace(&self, next: Next<Y, R>) -> Next<Y, R> {
+            if let Next::Yield(_) = next {
+                self.yields.set(self.yields.get() + 1);
+            }
+            mem::replace(&mut self.next.borrow_mut(), next)
+        }
+    }
+
+    pub struct Gen<Y, R, F: Future> {
+        airlock: CountingAirlock<Y, R>,
+        future: Pin<Box<F>>,
+    }
+
+    impl<Y, R, F: Future> Gen<Y, R, F> {
+        pub fn new(producer: impl FnOnce(Co<CountingAirlock<Y, R>>) -> F) -> Self {
+            let airlock = CountingAirlock {
+                next: Rc::new(RefCell::new(Next::Empty)),
+                yields: Rc::new(Cell::new(0)),
+            };
+            let future = Box::pin(producer(Co::new(airlock.clone())));
+            Self { airlock, future }
+        }
+
+        pub fn yields(&self) -> usize {
+            self.airlock.yields.get()
+        }
+    }
+
+    impl<Y, R, F: Future> Coroutine for Gen<Y, R, F> {
+        type Yield = Y;
+        type Resume = R;
+        type Return = F::Output;
+
+        fn resume_with(
+            mut self: Pin<&mut Self>,
+            arg: R,
+        ) -> GeneratorState<Self::Yield, Self::Return> {
+            let this = &mut *self;
+            this.airlock.replace(Next::Resume(arg));
+            advance(this.future.as_mut(), &this.airlock)
+        }
+    }
+
+    mod conformance {
+        genawaiter::engine_conformance_tests!(super::Gen::new);
+    }
+
+    #[test]
+    fn counts_yields() {
+        let mut gen = Gen::new(|mut co| {
+            async move {
+                co.yield_(1).await;
+                co.yield_(2).await;
+            }
+        });
+        while let GeneratorState::Yielded(_) = Pin::new(&mut gen).resume_with(()) {}
+        assert_eq!(gen.yields(), 2);
+    }
+}
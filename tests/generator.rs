@@ -0,0 +1,289 @@
+#![cfg(feature = "proc_macro")]
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![warn(clippy::pedantic)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+
+use genawaiter::{generator, yield_};
+
+#[generator(yield = u32)]
+fn odds(limit: u32) -> impl Iterator<Item = u32> {
+    for n in (1..limit).step_by(2) {
+        yield_!(n);
+    }
+}
+
+#[test]
+fn iterator() {
+    assert_eq!(odds(10).collect::<Vec<_>>(), [1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn yield_type_from_return_type() {
+    #[generator]
+    fn squares(limit: u64) -> impl Iterator<Item = u64> {
+        for n in 1..limit {
+            yield_!(n * n);
+        }
+    }
+
+    assert_eq!(squares(4).collect::<Vec<_>>(), [1, 4, 9]);
+}
+
+#[test]
+fn borrows_arguments() {
+    #[generator]
+    fn words<'a>(text: &'a str, min_len: usize) -> impl Iterator<Item = &'a str> + 'a {
+        for word in text.split(' ') {
+            if word.len() < min_len {
+                continue;
+            }
+            yield_!(word);
+        }
+    }
+
+    let text = String::from("a bb ccc");
+    assert_eq!(words(&text, 2).collect::<Vec<_>>(), ["bb", "ccc"]);
+}
+
+#[test]
+fn early_return() {
+    #[generator]
+    fn until_zero(items: Vec<i32>) -> impl Iterator<Item = i32> {
+        for item in items {
+            if item == 0 {
+                return;
+            }
+            yield_!(item);
+        }
+    }
+
+    assert_eq!(until_zero(vec![1, 2, 0, 3]).collect::<Vec<_>>(), [1, 2]);
+}
+
+#[test]
+fn send() {
+    #[generator]
+    fn odds_send(limit: u32) -> impl Iterator<Item = u32> + Send {
+        for n in (1..limit).step_by(2) {
+            yield_!(n);
+        }
+    }
+
+    let items = std::thread::spawn(|| odds_send(6).collect::<Vec<_>>())
+        .join()
+        .unwrap();
+    assert_eq!(items, [1, 3, 5]);
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn stream() {
+    use futures::{executor::block_on_stream, Stream};
+
+    #[generator(yield = u32)]
+    async fn countdown(from: u32) -> impl Stream<Item = u32> {
+        for n in (1..=from).rev() {
+            yield_!(n);
+        }
+    }
+
+    let items: Vec<_> = block_on_stream(Box::pin(countdown(3))).collect();
+    assert_eq!(items, [3, 2, 1]);
+}
+
+struct Countdown {
+    from: u32,
+    steps: Vec<u32>,
+}
+
+impl Countdown {
+    #[generator]
+    fn iter(&self) -> impl Iterator<Item = u32> {
+        for n in (1..=self.from).rev() {
+            yield_!(n);
+        }
+    }
+
+    #[generator]
+    fn drain(&mut self) -> impl Iterator<Item = u32> {
+        while let Some(step) = self.steps.pop() {
+            yield_!(step);
+        }
+    }
+
+    #[generator]
+    fn into_steps(self) -> impl Iterator<Item = u32> {
+        for step in self.steps {
+            yield_!(step);
+        }
+    }
+}
+
+#[test]
+fn methods() {
+    let mut countdown = Countdown {
+        from: 3,
+        steps: vec![1, 2],
+    };
+    assert_eq!(countdown.iter().collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!(countdown.drain().collect::<Vec<_>>(), [2, 1]);
+    assert!(countdown.steps.is_empty());
+
+    countdown.steps.push(5);
+    assert_eq!(countdown.into_steps().collect::<Vec<_>>(), [5]);
+}
+
+trait Steps {
+    fn steps(&self) -> Box<dyn Iterator<Item = u32> + '_>;
+
+    #[generator]
+    fn doubled(&self) -> Box<dyn Iterator<Item = u32>> {
+        for step in self.steps() {
+            yield_!(step * 2);
+        }
+    }
+}
+
+impl Steps for Countdown {
+    #[generator]
+    fn steps(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        for &step in &self.steps {
+            yield_!(step);
+        }
+    }
+}
+
+#[test]
+fn trait_methods() {
+    let countdown = Countdown {
+        from: 0,
+        steps: vec![1, 2],
+    };
+    assert_eq!(countdown.steps().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(countdown.doubled().collect::<Vec<_>>(), [2, 4]);
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn async_trait_method() {
+    use futures::{executor::block_on_stream, Stream};
+    use std::pin::Pin;
+
+    trait Ticks {
+        fn ticks(&self) -> Pin<Box<dyn Stream<Item = u32> + Send + '_>>;
+    }
+
+    struct Clock(u32);
+
+    impl Ticks for Clock {
+        #[generator]
+        async fn ticks(&self) -> Pin<Box<dyn Stream<Item = u32> + Send + '_>> {
+            for n in 0..self.0 {
+                yield_!(n);
+            }
+        }
+    }
+
+    let items: Vec<_> = block_on_stream(Clock(3).ticks()).collect();
+    assert_eq!(items, [0, 1, 2]);
+}
+
+#[test]
+fn into_iter_for_generic_type() {
+    use genawaiter::into_iter;
+
+    struct Pairs<T> {
+        items: Vec<T>,
+    }
+
+    #[into_iter]
+    impl<T: PartialOrd> Pairs<T> {
+        #[into_iter]
+        fn pairs(&self) -> impl Iterator<Item = (&T, &T)> {
+            for (i, a) in self.items.iter().enumerate() {
+                for b in &self.items[i + 1..] {
+                    if a < b {
+                        yield_!((a, b));
+                    }
+                }
+            }
+        }
+    }
+
+    let pairs = Pairs {
+        items: vec![1, 3, 2],
+    };
+    let mut res = Vec::new();
+    for (a, b) in &pairs {
+        res.push((*a, *b));
+    }
+    assert_eq!(res, [(1, 3), (1, 2)]);
+    // The `IntoIter` type can be named.
+    let iter: <&Pairs<i32> as IntoIterator>::IntoIter = pairs.into_iter();
+    assert_eq!(iter.count(), 2);
+}
+
+#[test]
+fn into_iter_for_mut_and_shared_refs() {
+    use genawaiter::into_iter;
+
+    struct Counters {
+        counts: Vec<u32>,
+    }
+
+    #[into_iter]
+    impl Counters {
+        #[into_iter(yield = &'a u32)]
+        fn counts<'a>(&'a self) {
+            for count in &self.counts {
+                yield_!(count);
+            }
+        }
+
+        #[into_iter(yield = &mut u32)]
+        fn counts_mut(&mut self) {
+            for count in &mut self.counts {
+                yield_!(count);
+            }
+        }
+    }
+
+    let mut counters = Counters { counts: vec![1, 2] };
+    for count in &mut counters {
+        *count *= 10;
+    }
+    assert_eq!((&counters).into_iter().collect::<Vec<_>>(), [&10, &20]);
+}
+
+#[test]
+fn into_iter_yielding_self() {
+    use genawaiter::into_iter;
+
+    struct Node {
+        name: &'static str,
+        children: Vec<Node>,
+    }
+
+    #[into_iter]
+    impl Node {
+        #[into_iter(yield = &Self)]
+        fn children(&self) {
+            for child in &self.children {
+                yield_!(child);
+            }
+        }
+    }
+
+    let leaf = |name| {
+        Node {
+            name,
+            children: Vec::new(),
+        }
+    };
+    let root = Node {
+        name: "root",
+        children: vec![leaf("a"), leaf("b")],
+    };
+    let names: Vec<_> = (&root).into_iter().map(|node| node.name).collect();
+    assert_eq!(names, ["a", "b"]);
+}
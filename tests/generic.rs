@@ -0,0 +1,99 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![warn(clippy::pedantic)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+
+use genawaiter::{inline, rc, stack::let_gen_using, sync, Airlock, Co, GeneratorState};
+use std::pin::pin;
+
+async fn odd_numbers_less_than_ten(mut co: Co<impl Airlock<Yield = i32, Resume = ()>>) {
+    for n in (1..).step_by(2).take_while(|&n| n < 10) {
+        co.yield_(n).await;
+    }
+}
+
+async fn running_total(mut co: Co<impl Airlock<Yield = i32, Resume = i32>>) -> i32 {
+    let mut total = 0;
+    for _ in 0..3 {
+        total += co.yield_(total).await;
+    }
+    total
+}
+
+#[test]
+fn rc() {
+    let gen = rc::Gen::new(odd_numbers_less_than_ten);
+    let xs: Vec<_> = gen.into_iter().collect();
+    assert_eq!(xs, [1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn sync() {
+    let gen = sync::Gen::new(odd_numbers_less_than_ten);
+    let xs: Vec<_> = gen.into_iter().collect();
+    assert_eq!(xs, [1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn stack() {
+    let_gen_using!(gen, odd_numbers_less_than_ten);
+    let xs: Vec<_> = gen.into_iter().collect();
+    assert_eq!(xs, [1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn inline() {
+    async fn producer(co: inline::Co<'_, i32>) {
+        odd_numbers_less_than_ten(co).await;
+    }
+
+    let gen = pin!(inline::Gen::new(producer));
+    let xs: Vec<_> = gen.collect();
+    assert_eq!(xs, [1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn resume_args() {
+    let mut gen = rc::Gen::new(running_total);
+    assert_eq!(gen.resume_with(0), GeneratorState::Yielded(0));
+    assert_eq!(gen.resume_with(1), GeneratorState::Yielded(1));
+    assert_eq!(gen.resume_with(2), GeneratorState::Yielded(3));
+    assert_eq!(gen.resume_with(3), GeneratorState::Complete(6));
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn proc_macro_fn() {
+    use genawaiter::{producer_fn, yield_};
+
+    #[producer_fn(u8)]
+    async fn odds() {
+        for n in (1_u8..).step_by(2).take_while(|&n| n < 10) {
+            yield_!(n);
+        }
+    }
+
+    let rc_items: Vec<_> = rc::Gen::new(odds).into_iter().collect();
+    let_gen_using!(gen, odds);
+    let stack_items: Vec<_> = gen.into_iter().collect();
+    assert_eq!(rc_items, [1, 3, 5, 7, 9]);
+    assert_eq!(stack_items, rc_items);
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn proc_macro_fn_with_args() {
+    use genawaiter::{producer_fn, yield_};
+
+    #[producer_fn(u8)]
+    async fn odds_below(limit: u8) {
+        for n in (1..).step_by(2).take_while(|&n| n < limit) {
+            yield_!(n);
+        }
+    }
+
+    let rc_items: Vec<_> = rc::Gen::new(odds_below(8)).into_iter().collect();
+    let_gen_using!(gen, odds_below(8));
+    let stack_items: Vec<_> = gen.into_iter().collect();
+    assert_eq!(rc_items, [1, 3, 5, 7]);
+    assert_eq!(stack_items, rc_items);
+}
@@ -49,11 +49,12 @@ fn stack_proc_macro_fn() {
     }
     let_gen_using!(gen, odds);
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
 #[test]
+#[allow(clippy::semicolon_if_nothing_returned)]
 fn stack_yield_a_func_call() {
     use genawaiter::{stack::producer_fn, yield_};
 
@@ -70,7 +71,7 @@ fn stack_yield_a_func_call() {
     }
     let_gen_using!(gen, odds);
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -87,7 +88,7 @@ fn stack_yield_loop_break() {
             }
             loop {
                 n += 1;
-                if n % 2 != 0 {
+                if n % 2 == 1 {
                     break yield_!(n);
                 }
             }
@@ -95,7 +96,7 @@ fn stack_yield_loop_break() {
     }
     let_gen_using!(gen, odds);
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -107,14 +108,14 @@ fn stack_yield_match() {
     async fn odds() {
         for n in (1_u8..).step_by(2).take_while(|&n| n < 10) {
             match Some(n) {
-                Some(n) if n % 2 != 0 => yield_!(n),
+                Some(n) if n % 2 == 1 => yield_!(n),
                 _ => {}
             }
         }
     }
     let_gen_using!(gen, odds);
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[test]
@@ -133,7 +134,7 @@ fn stack_yield_closure_no_macro() {
     };
 
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -155,7 +156,7 @@ fn stack_yield_closure() {
         )
     };
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -171,7 +172,7 @@ fn stack_convenience_macro() {
         }
     });
     let res = generator.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -190,3 +191,101 @@ fn stack_convenience_macro_resume() {
     assert_eq!(gen.resume_with("abc"), GeneratorState::Yielded(20));
     assert_eq!(gen.resume_with("def"), GeneratorState::Complete(()));
 }
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn stack_proc_macro_fn_with_args() {
+    use genawaiter::{stack::producer_fn, yield_};
+
+    #[producer_fn(&'a str)]
+    async fn words<'a>(text: &'a str, skip: usize) {
+        for word in text.split(' ').skip(skip) {
+            yield_!(word);
+        }
+    }
+
+    let text = String::from("one two three");
+    let_gen_using!(gen, words(&text, 1));
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec!["two", "three"], res);
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn stack_proc_macro_fn_resume() {
+    use genawaiter::{stack::producer_fn, yield_, GeneratorState};
+
+    #[producer_fn(u8, resume = &'static str)]
+    async fn echo(first: u8) -> &'static str {
+        let resume_arg = yield_!(first);
+        assert_eq!(resume_arg, "abc");
+        yield_!(first + 1)
+    }
+
+    let_gen_using!(gen, echo(10));
+    assert_eq!(gen.resume_with("ignored"), GeneratorState::Yielded(10));
+    assert_eq!(gen.resume_with("abc"), GeneratorState::Yielded(11));
+    assert_eq!(gen.resume_with("def"), GeneratorState::Complete("def"));
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn stack_convenience_macro_typed() {
+    use genawaiter::{stack::let_gen, yield_, GeneratorState};
+
+    let_gen!(gen, yield u8, resume &str, return usize => {
+        let mut total = 0;
+        loop {
+            let resume_arg = yield_!(10);
+            if resume_arg.is_empty() {
+                return total;
+            }
+            total += resume_arg.len();
+        }
+    });
+
+    assert_eq!(gen.resume_with("ignored"), GeneratorState::Yielded(10));
+    assert_eq!(gen.resume_with("abc"), GeneratorState::Yielded(10));
+    assert_eq!(gen.resume_with(""), GeneratorState::Complete(3));
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn stack_convenience_macro_named_co() {
+    use genawaiter::{
+        stack::{let_gen, let_gen_using, Co},
+        stack_producer as producer,
+        yield_,
+    };
+
+    async fn twice(co: &mut Co<'_, i32>, n: i32) {
+        co.yield_(n).await;
+        co.yield_(n).await;
+    }
+
+    let_gen!(gen, |co| {
+        yield_!(1);
+        twice(&mut co, 2).await;
+    });
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![1, 2, 2], res);
+
+    let_gen_using!(gen, producer!(|co| twice(&mut co, 3).await));
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![3, 3], res);
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn stack_convenience_macro_send() {
+    use genawaiter::{stack::let_send_gen, yield_, GeneratorState};
+    use std::thread;
+
+    let_send_gen!(gen, yield i32, return &str => {
+        yield_!(10);
+        "done"
+    });
+    assert_eq!(gen.resume(), GeneratorState::Yielded(10));
+    let state = thread::scope(|s| s.spawn(|| gen.resume()).join().unwrap());
+    assert_eq!(state, GeneratorState::Complete("done"));
+}
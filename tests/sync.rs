@@ -47,7 +47,7 @@ fn sync_proc_macro_fn() {
     }
     let gen = Gen::new(odds);
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -63,11 +63,11 @@ fn sync_proc_macro_closure() {
         }
     }));
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
-#[allow(clippy::let_unit_value)]
+#[allow(clippy::clone_on_copy, clippy::let_unit_value, clippy::unit_arg)]
 #[test]
 fn sync_proc_macro_fn_method_call() {
     use genawaiter::{sync::producer_fn, yield_};
@@ -75,12 +75,12 @@ fn sync_proc_macro_fn_method_call() {
     #[producer_fn(u8)]
     async fn odds() {
         for n in (1_u8..).step_by(2).take_while(|&n| n < 10) {
-            let _ = yield_!(n).clone();
+            let () = yield_!(n).clone();
         }
     }
     let gen = genawaiter::sync::Gen::new(odds);
     let res = gen.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -96,7 +96,7 @@ fn sync_convenience_macro() {
         }
     });
     let res = g.into_iter().collect::<Vec<_>>();
-    assert_eq!(vec![1, 3, 5, 7, 9], res)
+    assert_eq!(vec![1, 3, 5, 7, 9], res);
 }
 
 #[cfg(feature = "proc_macro")]
@@ -115,3 +115,178 @@ fn sync_convenience_macro_resume() {
     assert_eq!(gen.resume_with("abc"), GeneratorState::Yielded(20));
     assert_eq!(gen.resume_with("def"), GeneratorState::Complete(()));
 }
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn sync_proc_macro_fn_with_args() {
+    use genawaiter::{sync::producer_fn, yield_};
+    use std::fmt::Display;
+
+    #[producer_fn(u8)]
+    async fn odds_below(limit: u8) {
+        for n in (1..).step_by(2).take_while(|&n| n < limit) {
+            yield_!(n);
+        }
+    }
+
+    #[producer_fn(String)]
+    async fn labeled<'a, T>(label: &str, items: &'a [T])
+    where
+        T: Display,
+    {
+        for item in items {
+            yield_!(format!("{}: {}", label, item));
+        }
+    }
+
+    let res = Gen::new(odds_below(8)).into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![1, 3, 5, 7], res);
+
+    let label = String::from("n");
+    let gen = Gen::new(labeled(&label, &[1, 2]));
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec!["n: 1", "n: 2"], res);
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn sync_proc_macro_fn_resume() {
+    use genawaiter::{sync::producer_fn, yield_, GeneratorState};
+
+    #[producer_fn(u8, resume = &'static str)]
+    async fn echo(first: u8) -> &'static str {
+        let resume_arg = yield_!(first);
+        assert_eq!(resume_arg, "abc");
+        yield_!(first + 1)
+    }
+
+    let mut gen = Gen::new(echo(10));
+    assert_eq!(gen.resume_with("ignored"), GeneratorState::Yielded(10));
+    assert_eq!(gen.resume_with("abc"), GeneratorState::Yielded(11));
+    assert_eq!(gen.resume_with("def"), GeneratorState::Complete("def"));
+}
+
+#[cfg(all(feature = "proc_macro", feature = "futures03"))]
+#[test]
+fn sync_stream_macro() {
+    use futures::{executor::block_on_stream, stream, Stream};
+    use genawaiter::{sync::stream, yield_};
+
+    fn pairs(
+        source: impl Stream<Item = u8> + Send,
+    ) -> impl Stream<Item = (u8, u8)> + Send {
+        stream!({
+            'outer: for await a in source {
+                for await b in stream::iter(0..a) {
+                    if a == 3 {
+                        continue 'outer;
+                    }
+                    yield_!((a, b));
+                }
+            }
+        })
+    }
+
+    let items: Vec<_> = block_on_stream(Box::pin(pairs(stream::iter(1..=3)))).collect();
+    assert_eq!(items, [(1, 0), (2, 0), (2, 1)]);
+}
+
+#[cfg(all(feature = "proc_macro", feature = "futures03"))]
+#[test]
+fn sync_try_stream_macro() {
+    use futures::{executor::block_on_stream, future, Stream};
+    use genawaiter::{sync::try_stream, yield_};
+
+    fn checked(limit: u32) -> impl Stream<Item = Result<u32, String>> {
+        try_stream! {
+            for n in 0..5 {
+                let n = future::ready(n).await;
+                if n > limit {
+                    Err(format!("{} is too big", n))?;
+                }
+                yield_!(n);
+            }
+        }
+    }
+
+    let items: Vec<_> = block_on_stream(Box::pin(checked(10))).collect();
+    assert_eq!(items, [Ok(0), Ok(1), Ok(2), Ok(3), Ok(4)]);
+    let items: Vec<_> = block_on_stream(Box::pin(checked(1))).collect();
+    assert_eq!(items, [Ok(0), Ok(1), Err("2 is too big".to_string())]);
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn sync_convenience_macro_typed() {
+    use genawaiter::{sync::gen, yield_, GeneratorState};
+
+    let mut gen = gen!(yield u8, resume &str, return usize => {
+        let mut total = 0;
+        loop {
+            let resume_arg = yield_!(10);
+            if resume_arg.is_empty() {
+                return total;
+            }
+            total += resume_arg.len();
+        }
+    });
+
+    assert_eq!(gen.resume_with("ignored"), GeneratorState::Yielded(10));
+    assert_eq!(gen.resume_with("abc"), GeneratorState::Yielded(10));
+    assert_eq!(gen.resume_with(""), GeneratorState::Complete(3));
+
+    let mut gen = gen!(return &str => {
+        yield_!(1);
+        "done"
+    });
+    assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+    assert_eq!(gen.resume(), GeneratorState::Complete("done"));
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn sync_convenience_macro_named_co() {
+    use genawaiter::{
+        sync::{gen, Co},
+        yield_,
+    };
+
+    async fn twice(co: &mut Co<i32>, n: i32) {
+        co.yield_(n).await;
+        co.yield_(n).await;
+    }
+
+    let gen = gen!(|co| {
+        yield_!(1);
+        twice(&mut co, 2).await;
+        yield_!(3);
+    });
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![1, 2, 2, 3], res);
+
+    let gen = gen!(yield i32 => |co| twice(&mut co, 4).await);
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![4, 4], res);
+}
+
+#[cfg(feature = "proc_macro")]
+#[test]
+fn sync_iter_gen_macro() {
+    use genawaiter::{sync::iter_gen, yield_};
+
+    let gen = iter_gen!({
+        for n in 1..4 {
+            yield_!(n);
+        }
+    });
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![1, 2, 3], res);
+
+    // Awaiting its own yields is fine.
+    let gen = iter_gen!(yield i32 => |co| {
+        co.yield_(1).await;
+        co.yield_(2).await;
+    });
+    let res = gen.into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![1, 2], res);
+}
@@ -0,0 +1,31 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![warn(clippy::pedantic)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+
+use genawaiter::thread::{Gen, Yielder};
+
+fn odd_numbers_less_than_ten(mut y: Yielder<i32>) {
+    for n in (1..).step_by(2).take_while(|&n| n < 10) {
+        y.yield_(n);
+    }
+}
+
+#[test]
+fn test_basic() {
+    let gen = Gen::new(odd_numbers_less_than_ten);
+    let xs: Vec<_> = gen.into_iter().collect();
+    assert_eq!(xs, [1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn test_callback() {
+    fn visit_digits(n: u32, mut visitor: impl FnMut(u32)) {
+        for c in n.to_string().chars() {
+            visitor(c.to_digit(10).unwrap());
+        }
+    }
+
+    let gen = Gen::new(|mut y| visit_digits(2020, |d| y.yield_(d)));
+    let xs: Vec<_> = gen.into_iter().collect();
+    assert_eq!(xs, [2, 0, 2, 0]);
+}
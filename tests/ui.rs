@@ -6,7 +6,22 @@ fn ui() {
     t.compile_fail("tests/ui/rc_fail_not_awaiting_yield.rs");
     t.compile_fail("tests/ui/stack_fail_not_awaiting_yield.rs");
     t.compile_fail("tests/ui/sync_fail_not_awaiting_yield.rs");
+    t.compile_fail("tests/ui/rc_fail_typed_gen_mismatch.rs");
+    t.compile_fail("tests/ui/rc_fail_typed_gen_duplicate.rs");
+
+    t.compile_fail("tests/ui/fail_named_co_not_awaited.rs");
+    t.compile_fail("tests/ui/fail_yield_outside_generator.rs");
+    t.compile_fail("tests/ui/fail_yield_in_closure.rs");
+    t.compile_fail("tests/ui/fail_yield_in_async_block.rs");
+    t.compile_fail("tests/ui/rc_fail_iter_gen_await.rs");
+    t.compile_fail("tests/ui/rc_fail_iter_gen_await_in_macro.rs");
+    t.compile_fail("tests/ui/rc_fail_iter_gen_await_in_lookalike_macro.rs");
+    t.compile_fail("tests/ui/fail_yield_without_value.rs");
 
     t.compile_fail("tests/ui/fail_producer_with_argument.rs");
+    t.compile_fail("tests/ui/generator_fail_without_impl_return.rs");
+    t.compile_fail("tests/ui/into_iter_fail_only_on_method.rs");
     t.compile_fail("tests/ui/stack_fail_when_co_is_static.rs");
+    t.compile_fail("tests/ui/inline_fail_when_co_escapes.rs");
+    t.compile_fail("tests/ui/inline_fail_when_co_returned.rs");
 }
@@ -0,0 +1,7 @@
+use genawaiter::rc::gen;
+
+fn main() {
+    let _gen = gen!(yield i32 => |co| {
+        co.yield_(10);
+    });
+}
@@ -0,0 +1,10 @@
+use genawaiter::{rc::gen, yield_};
+
+fn main() {
+    let _gen = gen!({
+        async {
+            yield_!(10);
+        }
+        .await;
+    });
+}
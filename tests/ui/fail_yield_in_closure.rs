@@ -0,0 +1,7 @@
+use genawaiter::{rc::gen, yield_};
+
+fn main() {
+    let _gen = gen!({
+        (0..3).for_each(|n| yield_!(n));
+    });
+}
@@ -0,0 +1,5 @@
+use genawaiter::yield_;
+
+fn main() {
+    yield_!(10);
+}
@@ -0,0 +1,7 @@
+use genawaiter::{rc::gen, yield_};
+
+fn main() {
+    let _gen = gen!({
+        yield_!();
+    });
+}
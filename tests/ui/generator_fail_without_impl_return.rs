@@ -0,0 +1,8 @@
+use genawaiter::generator;
+
+#[generator(yield = u8)]
+fn odds() -> Vec<u8> {
+    vec![1, 3, 5]
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use genawaiter::inline::{Co, Gen};
+use std::cell::RefCell;
+
+fn main() {
+    let stash: RefCell<Option<Co<'_, i32>>> = RefCell::new(None);
+    let gen = Gen::new(|co| {
+        *stash.borrow_mut() = Some(co);
+        async {}
+    });
+    let _ = gen;
+}
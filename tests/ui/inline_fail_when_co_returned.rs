@@ -0,0 +1,10 @@
+use genawaiter::inline::{Co, Gen};
+
+async fn produce(co: Co<'_, String>) -> Co<'_, String> {
+    co
+}
+
+fn main() {
+    let gen = Gen::new(produce);
+    let _ = gen;
+}
@@ -0,0 +1,14 @@
+use genawaiter::{into_iter, yield_};
+
+struct Digits(Vec<u8>);
+
+impl Digits {
+    #[into_iter(yield = &u8)]
+    fn digits(&self) {
+        for digit in &self.0 {
+            yield_!(digit);
+        }
+    }
+}
+
+fn main() {}
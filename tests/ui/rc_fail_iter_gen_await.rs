@@ -0,0 +1,12 @@
+use genawaiter::{rc::iter_gen, yield_};
+
+async fn fetch() -> i32 {
+    10
+}
+
+fn main() {
+    let _gen = iter_gen!({
+        let n = fetch().await;
+        yield_!(n);
+    });
+}
@@ -0,0 +1,31 @@
+use genawaiter::{rc::iter_gen, yield_};
+
+async fn fetch() -> i32 {
+    10
+}
+
+macro_rules! my_gen {
+    ($value:expr) => {
+        $value
+    };
+}
+
+mod other {
+    macro_rules! stream {
+        ($value:expr) => {
+            $value
+        };
+    }
+    pub(crate) use stream;
+}
+
+fn main() {
+    let _gen = iter_gen!({
+        let n = my_gen!(fetch().await);
+        yield_!(n);
+    });
+    let _gen = iter_gen!({
+        let n = other::stream!(fetch().await);
+        yield_!(n);
+    });
+}
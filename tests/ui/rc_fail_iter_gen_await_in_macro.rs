@@ -0,0 +1,29 @@
+use genawaiter::{rc::iter_gen, yield_};
+
+async fn fetch() -> i32 {
+    10
+}
+
+macro_rules! unknown {
+    ($($tokens:tt)*) => {
+        $($tokens)*
+    };
+}
+
+fn main() {
+    let _gen = iter_gen!({
+        let n = dbg!(fetch().await);
+        yield_!(n);
+    });
+    let _gen = iter_gen!({
+        let ns = vec![1, fetch().await];
+        yield_!(ns);
+    });
+    let _gen = iter_gen!({
+        yield_!(fetch().await);
+    });
+    let _gen = iter_gen!({
+        unknown! { let n = fetch().await; }
+        yield_!(());
+    });
+}
@@ -0,0 +1,5 @@
+use genawaiter::rc::gen;
+
+fn main() {
+    let _gen = gen!(yield u8, yield u16 => {});
+}
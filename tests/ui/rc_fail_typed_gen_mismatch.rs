@@ -0,0 +1,8 @@
+use genawaiter::{rc::gen, yield_};
+
+fn main() {
+    let _gen = gen!(yield u8, return usize => {
+        yield_!("not a number");
+        "not a number either"
+    });
+}